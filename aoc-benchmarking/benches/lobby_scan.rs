@@ -0,0 +1,42 @@
+//! Compare the ways of picking batteries for day 3 against `best_joltage`,
+//! which the solver uses unless the `swar` feature is on.
+use criterion::{Criterion, criterion_group, criterion_main};
+use lobby::{best_joltage, best_joltage_by, scan};
+
+fn lobby_scan(c: &mut Criterion) {
+    let input =
+        std::fs::read_to_string("../day-003-lobby/input.txt").expect("Could not load input");
+    let banks: Vec<&str> = input.trim().lines().collect();
+
+    for k in [2, 12] {
+        let mut group = c.benchmark_group(format!("003 lobby scan (k = {})", k));
+
+        // what the solver uses unless the `swar` feature is on
+        group.bench_function("best_joltage (baseline)", |b| {
+            b.iter(|| {
+                for bank in banks.iter() {
+                    best_joltage(bank, k).expect("Failed to pick");
+                }
+            })
+        });
+        group.bench_function("scalar", |b| {
+            b.iter(|| {
+                for bank in banks.iter() {
+                    best_joltage_by(bank, k, scan::max_digit_scalar).expect("Failed to pick");
+                }
+            })
+        });
+        group.bench_function("swar", |b| {
+            b.iter(|| {
+                for bank in banks.iter() {
+                    best_joltage_by(bank, k, scan::max_digit_swar).expect("Failed to pick");
+                }
+            })
+        });
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, lobby_scan);
+criterion_main!(benches);
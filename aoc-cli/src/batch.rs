@@ -0,0 +1,404 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result, bail};
+use aoc_plumbing::{DynAnswer, DynSolution};
+use clap::Args;
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::{
+    cli::{print_solution, problems, solve_dyn},
+    config::Config,
+    style::Painter,
+    workspace::find_day_dir,
+};
+
+/// Run every implemented day of a year against its conventional input.
+///
+/// Days without a non-empty `day-NNN-*/input.txt` are skipped.
+#[derive(Args)]
+pub(crate) struct RunAll {
+    /// The year to run.
+    ///
+    /// Defaults to the `year` from the config.
+    #[clap(short, long)]
+    year: Option<usize>,
+
+    /// The directory containing the `day-NNN-*` crates.
+    ///
+    /// Defaults to the `input_root` from the config.
+    #[clap(long)]
+    root: Option<PathBuf>,
+
+    #[clap(flatten)]
+    opts: BatchOpts,
+}
+
+impl RunAll {
+    pub fn run(&self, config: &Config) -> Result<()> {
+        let root = config.input_root(self.root.as_ref());
+        let year = config.year(self.year);
+
+        let mut jobs = Vec::default();
+        for problem in problems().into_iter().filter(|p| p.year == year) {
+            let input = find_day_dir(&root, year, problem.day)?.map(|d| d.join("input.txt"));
+            match input {
+                Some(input) if input.metadata().map(|m| m.len() > 0).unwrap_or(false) => {
+                    jobs.push(Job {
+                        year,
+                        day: problem.day,
+                        input,
+                    })
+                }
+                _ => eprintln!("skipping {} day {}: no input", year, problem.day),
+            }
+        }
+
+        self.opts.run(&jobs, config)
+    }
+}
+
+/// Solve many inputs, possibly for many days, in parallel.
+///
+/// Each job is given as `DAY:PATH`, e.g. `3:inputs/alice/day-03.txt`.
+#[derive(Args)]
+pub(crate) struct Batch {
+    /// The jobs to run, as `DAY:PATH`.
+    #[clap(required = true, value_name = "DAY:PATH")]
+    specs: Vec<JobSpec>,
+
+    /// The year of the days to run.
+    ///
+    /// Defaults to the `year` from the config.
+    #[clap(short, long)]
+    year: Option<usize>,
+
+    #[clap(flatten)]
+    opts: BatchOpts,
+}
+
+impl Batch {
+    pub fn run(&self, config: &Config) -> Result<()> {
+        let year = config.year(self.year);
+        let jobs: Vec<_> = self
+            .specs
+            .iter()
+            .map(|spec| Job {
+                year,
+                day: spec.day,
+                input: spec.input.clone(),
+            })
+            .collect();
+
+        self.opts.run(&jobs, config)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct JobSpec {
+    day: usize,
+    input: PathBuf,
+}
+
+impl FromStr for JobSpec {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (day, input) = s
+            .split_once(':')
+            .with_context(|| format!("expected DAY:PATH, got '{}'", s))?;
+        let day = day
+            .parse()
+            .with_context(|| format!("invalid day '{}' in '{}'", day, s))?;
+        if input.is_empty() {
+            bail!("missing path in '{}'", s);
+        }
+
+        Ok(Self {
+            day,
+            input: PathBuf::from(input),
+        })
+    }
+}
+
+#[derive(Args)]
+struct BatchOpts {
+    /// The number of jobs to run at once.
+    ///
+    /// Defaults to the number of cpus.
+    #[clap(short = 'j', long)]
+    jobs: Option<usize>,
+
+    /// Only solve one input at a time, so timings aren't skewed by the other
+    /// jobs.
+    ///
+    /// Inputs are still read in parallel.
+    #[clap(long)]
+    serial_timing: bool,
+
+    /// Display the output as json.
+    ///
+    /// This may be specified instead by setting `output = "json"` in the
+    /// config, or by setting the `AOC_JSON` env var to `true`.
+    #[clap(long)]
+    json: bool,
+}
+
+impl BatchOpts {
+    fn run(&self, jobs: &[Job], config: &Config) -> Result<()> {
+        let outcomes = run_jobs(jobs, self.jobs, self.serial_timing)?;
+        let painter = Painter::new(config.colors.value);
+
+        if config.json(self.json) {
+            let out: Vec<_> = outcomes.iter().map(Outcome::to_json).collect();
+            println!("{}", serde_json::to_string(&out)?);
+        } else {
+            for outcome in &outcomes {
+                outcome.print(&painter);
+            }
+        }
+
+        let failed = outcomes.iter().filter(|o| o.result.is_err()).count();
+        if failed > 0 {
+            bail!("{} of {} jobs failed", failed, outcomes.len());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Job {
+    year: usize,
+    day: usize,
+    input: PathBuf,
+}
+
+#[derive(Debug)]
+struct Outcome<'a> {
+    job: &'a Job,
+    result: Result<DynSolution, String>,
+    /// Just the time spent solving, excluding reading the input.
+    elapsed: Duration,
+}
+
+/// An [`Outcome`] as json, with either the answers and timing or the error.
+#[derive(Debug, Serialize)]
+struct OutcomeJson<'a> {
+    year: usize,
+    day: usize,
+    input: &'a Path,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part_one: Option<&'a DynAnswer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part_two: Option<&'a DynAnswer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    elapsed_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+}
+
+impl Outcome<'_> {
+    fn to_json(&self) -> OutcomeJson<'_> {
+        let solution = self.result.as_ref().ok();
+        OutcomeJson {
+            year: self.job.year,
+            day: self.job.day,
+            input: &self.job.input,
+            part_one: solution.map(|s| &s.part_one),
+            part_two: solution.map(|s| &s.part_two),
+            elapsed_ms: solution.map(|_| self.elapsed.as_secs_f64() * 1000.0),
+            error: self.result.as_ref().err().map(String::as_str),
+        }
+    }
+
+    fn print(&self, painter: &Painter) {
+        println!(
+            "{}",
+            painter.bold(format!(
+                "{} day {} ({})",
+                self.job.year,
+                self.job.day,
+                self.job.input.display()
+            ))
+        );
+
+        match &self.result {
+            Ok(solution) => {
+                print_solution(&solution.part_one, &solution.part_two, painter);
+                println!(
+                    "{}",
+                    painter.dim(format!(
+                        "solved in {:.3} ms",
+                        self.elapsed.as_secs_f64() * 1000.0
+                    ))
+                );
+            }
+            Err(e) => println!("{} {}", painter.red("error:"), e),
+        }
+    }
+}
+
+/// Run every job on a pool of `threads` threads, returning the outcomes in the
+/// same order as the jobs.
+fn run_jobs(jobs: &[Job], threads: Option<usize>, serial_timing: bool) -> Result<Vec<Outcome<'_>>> {
+    run_jobs_with(jobs, threads, serial_timing, solve_dyn)
+}
+
+/// [`run_jobs`], solving with `solve` so tests can swap it out.
+fn run_jobs_with<F>(
+    jobs: &[Job],
+    threads: Option<usize>,
+    serial_timing: bool,
+    solve: F,
+) -> Result<Vec<Outcome<'_>>>
+where
+    F: Fn(usize, usize, &str) -> Option<Result<DynSolution>> + Sync,
+{
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads.unwrap_or_default())
+        .build()
+        .context("Could not start the thread pool")?;
+
+    // held for the duration of each solve when timings should be isolated
+    let solving = Mutex::new(());
+
+    Ok(pool.install(|| {
+        jobs.par_iter()
+            .map(|job| {
+                let input = match read_input(&job.input) {
+                    Ok(input) => input,
+                    Err(e) => {
+                        return Outcome {
+                            job,
+                            result: Err(format!("{:#}", e)),
+                            elapsed: Duration::ZERO,
+                        };
+                    }
+                };
+
+                let _guard =
+                    serial_timing.then(|| solving.lock().unwrap_or_else(|e| e.into_inner()));
+                let start = Instant::now();
+                // a panic should only fail its own job, not the whole batch
+                let result =
+                    panic::catch_unwind(AssertUnwindSafe(|| solve(job.year, job.day, &input)));
+                let elapsed = start.elapsed();
+
+                let result = match result {
+                    Ok(Some(Ok(solution))) => Ok(solution),
+                    Ok(Some(Err(e))) => Err(format!("{:#}", e)),
+                    Ok(None) => Err(format!("{} day {} is not implemented", job.year, job.day)),
+                    Err(_) => Err("the solver panicked".to_string()),
+                };
+
+                Outcome {
+                    job,
+                    result,
+                    elapsed,
+                }
+            })
+            .collect()
+    }))
+}
+
+fn read_input(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path).with_context(|| format!("Could not read {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn job_specs() {
+        assert_eq!(
+            "3:inputs/day-03.txt".parse::<JobSpec>().unwrap(),
+            JobSpec {
+                day: 3,
+                input: PathBuf::from("inputs/day-03.txt"),
+            }
+        );
+        assert!("3".parse::<JobSpec>().is_err());
+        assert!("x:foo".parse::<JobSpec>().is_err());
+        assert!("3:".parse::<JobSpec>().is_err());
+    }
+
+    #[test]
+    fn runs_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let good = dir.path().join("good.txt");
+        let bad = dir.path().join("bad.txt");
+        std::fs::write(&good, "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82").unwrap();
+        std::fs::write(&bad, "L").unwrap();
+
+        let job = |day, input: &Path| Job {
+            year: 2025,
+            day,
+            input: input.to_path_buf(),
+        };
+        let jobs = vec![
+            job(1, &good),
+            job(1, &bad),
+            job(1, &dir.path().join("missing.txt")),
+            job(26, &good),
+            job(1, &good),
+        ];
+
+        for serial_timing in [false, true] {
+            let outcomes = run_jobs(&jobs, Some(2), serial_timing).unwrap();
+            assert_eq!(outcomes.len(), jobs.len());
+            for (outcome, job) in outcomes.iter().zip(&jobs) {
+                assert_eq!(outcome.job, job);
+            }
+
+            let first = outcomes[0].result.as_ref().unwrap();
+            assert_eq!(first.to_string(), "part 1: 3\npart 2: 6");
+            assert!(outcomes[1].result.is_err());
+            assert!(outcomes[2].result.is_err());
+            assert!(
+                outcomes[3]
+                    .result
+                    .as_ref()
+                    .unwrap_err()
+                    .contains("not implemented")
+            );
+            assert!(outcomes[4].result.is_ok());
+        }
+    }
+
+    #[test]
+    fn panics_fail_one_job() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("input.txt");
+        std::fs::write(&input, "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82").unwrap();
+
+        let jobs: Vec<_> = [1, 2, 1]
+            .into_iter()
+            .map(|day| Job {
+                year: 2025,
+                day,
+                input: input.clone(),
+            })
+            .collect();
+
+        let outcomes = run_jobs_with(&jobs, Some(2), false, |year, day, input| {
+            assert_ne!(day, 2, "boom");
+            solve_dyn(year, day, input)
+        })
+        .unwrap();
+
+        assert!(outcomes[0].result.is_ok());
+        assert_eq!(
+            outcomes[1].result.as_ref().unwrap_err(),
+            "the solver panicked"
+        );
+        assert!(outcomes[2].result.is_ok());
+    }
+}
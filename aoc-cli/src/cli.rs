@@ -1,30 +1,43 @@
 use std::{
+    fmt::Display,
     marker::PhantomData,
     path::{Path, PathBuf},
+    time::{Duration, Instant},
 };
 
 use anyhow::{Context, Result};
-use aoc_plumbing::Problem;
-use cafeteria::Cafeteria;
-use christmas_tree_farm::ChristmasTreeFarm;
+use aoc_plumbing::{DynSolution, Problem};
+use aoc2025::{ProblemInfo, problems::*};
 use clap::{Args, CommandFactory, Parser, Subcommand};
 use clap_complete::{generate, shells::Zsh};
-use factory::Factory;
-use gift_shop::GiftShop;
-use laboratories::Laboratories;
-use lobby::Lobby;
-use movie_theater::MovieTheater;
-use playground::Playground;
-use printing_department::PrintingDepartment;
-use reactor::Reactor;
-use secret_entrance::SecretEntrance;
-use trash_compactor::TrashCompactor;
 // import_marker
 
+#[cfg(feature = "trace")]
+use crate::explain::Explain;
+use crate::{
+    batch::{Batch, RunAll},
+    config::{Config, ConfigCmd},
+    fetch::Fetch,
+    new::New,
+    serve::Serve,
+    style::Painter,
+    submit::Submit,
+    viz::Viz,
+    watch::Watch,
+};
+
 // I'm not proud
 macro_rules! generate_cli {
-    ($(($name:ident, $day:literal)),* $(,)?) => {
-        /// Advent of Code solutions for 2025
+    ($(($name:ident, $krate:ident, $feature:literal, $year:literal, $day:literal $(, $viz:ident)?)),* $(,)?) => {
+        // make sure the registrations agree with the problems themselves
+        $(
+        const _: () = assert!(
+            <$name as Problem>::YEAR == $year && <$name as Problem>::DAY == $day,
+            concat!("mismatched registration for ", stringify!($name))
+        );
+        )*
+
+        /// Advent of Code solutions
         #[derive(Parser)]
         #[command(name = "aoc", author, version)]
         #[command(help_template = "\
@@ -38,7 +51,12 @@ macro_rules! generate_cli {
         impl Cli {
             pub fn run() -> Result<()> {
                 let command = Self::parse().command;
-                command.run()
+                // a typo in one layer of the config shouldn't lose the rest
+                let (config, skipped) = Config::load();
+                for e in skipped {
+                    eprintln!("warning: skipped part of the config, {:#}", e);
+                }
+                command.run(&config)
             }
         }
 
@@ -54,15 +72,57 @@ macro_rules! generate_cli {
 
             #[command(display_order = 31)]
             GenerateCompletions(GenerateCompletions),
+
+            #[command(display_order = 32)]
+            New(New),
+
+            #[command(display_order = 33)]
+            Fetch(Fetch),
+
+            #[command(display_order = 34)]
+            Submit(Submit),
+
+            #[command(display_order = 35)]
+            Config(ConfigCmd),
+
+            #[command(display_order = 36)]
+            Serve(Serve),
+
+            #[command(display_order = 37)]
+            RunAll(RunAll),
+
+            #[command(display_order = 38)]
+            Batch(Batch),
+
+            #[command(display_order = 39)]
+            Watch(Watch),
+
+            #[command(display_order = 40)]
+            Viz(Viz),
+
+            #[cfg(feature = "trace")]
+            #[command(display_order = 41)]
+            Explain(Explain),
         }
 
         impl Commands {
-            pub fn run(&self) -> Result<()> {
+            pub fn run(&self, config: &Config) -> Result<()> {
                 match self {
                     Self::GenerateCompletions(cmd) => cmd.run(),
-                    Self::Run(cmd) => cmd.run(),
+                    Self::Run(cmd) => cmd.run(config),
+                    Self::New(cmd) => cmd.run(config),
+                    Self::Fetch(cmd) => cmd.run(config),
+                    Self::Submit(cmd) => cmd.run(config),
+                    Self::Config(cmd) => cmd.run(config),
+                    Self::Serve(cmd) => cmd.run(config),
+                    Self::RunAll(cmd) => cmd.run(config),
+                    Self::Batch(cmd) => cmd.run(config),
+                    Self::Watch(cmd) => cmd.run(config),
+                    Self::Viz(cmd) => cmd.run(config),
+                    #[cfg(feature = "trace")]
+                    Self::Explain(cmd) => cmd.run(config),
                     $(
-                    Self::$name(cmd) => cmd.run(),
+                    Self::$name(cmd) => cmd.run(config),
                     )*
                 }
             }
@@ -73,6 +133,12 @@ macro_rules! generate_cli {
         /// The day must be implemented and the specified input must exist.
         #[derive(Args)]
         pub(crate) struct Run {
+            /// The year of the day to run.
+            ///
+            /// Defaults to the `year` from the config.
+            #[clap(short, long)]
+            year: Option<usize>,
+
             /// The day to run.
             ///
             /// This may be specified instead by setting the `AOC_DAY` env var.
@@ -91,21 +157,22 @@ macro_rules! generate_cli {
 
             /// Display the output as json.
             ///
-            /// This may be specified instead by setting the `AOC_INPUT` env
-            /// var to `true`. If the flag is passed, on the command line, it
-            /// will take precendence over the env var.
-            #[clap(short, long, env = "AOC_JSON")]
+            /// This may be specified instead by setting `output = "json"` in
+            /// the config, or by setting the `AOC_JSON` env var to `true`. If
+            /// the flag is passed on the command line, it will take
+            /// precendence over both.
+            #[clap(short, long)]
             json: bool,
         }
 
         impl Run {
-            pub fn run(&self) -> Result<()> {
-                match self.day {
+            pub fn run(&self, config: &Config) -> Result<()> {
+                match (config.year(self.year), self.day) {
                     $(
-                    $day => _run::<$name>(&self.input, self.json),
+                    ($year, $day) => _run::<$name>(&self.input, self.json, config),
                     )*
                     _ => {
-                        if self.json {
+                        if config.json(self.json) {
                             println!("\"not implemented\"");
                         } else {
                             println!("not implemented");
@@ -115,6 +182,26 @@ macro_rules! generate_cli {
                 }
             }
         }
+
+        /// Solve the given day with the given input, if that day is
+        /// implemented.
+        pub(crate) fn solve_dyn(year: usize, day: usize, input: &str) -> Option<Result<DynSolution>> {
+            match (year, day) {
+                $(
+                ($year, $day) => Some(_solve_dyn::<$name>(input)),
+                )*
+                _ => None,
+            }
+        }
+
+        /// Every registered problem, in registration order.
+        pub(crate) fn problems() -> Vec<ProblemInfo> {
+            vec![
+                $(
+                ProblemInfo::of::<$name>(),
+                )*
+            ]
+        }
     };
 }
 
@@ -139,31 +226,68 @@ where
     T: Problem,
     <T as Problem>::ProblemError: Into<anyhow::Error>,
 {
-    pub fn run(&self) -> Result<()> {
-        _run::<T>(&self.input, self.json)
+    pub fn run(&self, config: &Config) -> Result<()> {
+        _run::<T>(&self.input, self.json, config)
     }
 }
 
-fn _run<T>(input_file: &Path, json: bool) -> Result<()>
+fn _run<T>(input_file: &Path, json: bool, config: &Config) -> Result<()>
 where
     T: Problem,
     <T as Problem>::ProblemError: Into<anyhow::Error>,
 {
     let input = std::fs::read_to_string(input_file).context("Could not read input file")?;
 
+    let start = Instant::now();
     let solution = T::solve(&input)
         .map_err(Into::<anyhow::Error>::into)
         .context("Failed to solve")?;
+    let elapsed = start.elapsed();
+
+    let painter = Painter::new(config.colors.value);
 
-    if json {
+    if config.json(json) {
         println!("{}", serde_json::to_string(&solution)?);
     } else {
-        println!("{}", solution);
+        print_solution(&solution.part_one, &solution.part_two, &painter);
+    }
+
+    if config.timing.value {
+        print_timing(elapsed, &painter);
     }
 
     Ok(())
 }
 
+/// Print a solution in plain text, matching the `Display` impl of `Solution`.
+pub(crate) fn print_solution(part_one: impl Display, part_two: impl Display, painter: &Painter) {
+    println!("{} {}", painter.dim("part 1:"), painter.bold(part_one));
+    println!("{} {}", painter.dim("part 2:"), painter.bold(part_two));
+}
+
+/// Timings go to stderr so they don't interfere with parsing the output.
+pub(crate) fn print_timing(elapsed: Duration, painter: &Painter) {
+    eprintln!(
+        "{}",
+        painter.dim(format!(
+            "solved in {:.3} ms",
+            elapsed.as_secs_f64() * 1000.0
+        ))
+    );
+}
+
+fn _solve_dyn<T>(input: &str) -> Result<DynSolution>
+where
+    T: Problem,
+    <T as Problem>::ProblemError: Into<anyhow::Error>,
+{
+    let solution = T::solve(input)
+        .map_err(Into::<anyhow::Error>::into)
+        .context("Failed to solve")?;
+
+    Ok(solution.to_dyn()?)
+}
+
 /// Generate zsh completions
 #[derive(Debug, Args)]
 pub struct GenerateCompletions;
@@ -175,18 +299,18 @@ impl GenerateCompletions {
     }
 }
 
-generate_cli! {
-    (SecretEntrance, 1),
-    (GiftShop, 2),
-    (Lobby, 3),
-    (PrintingDepartment, 4),
-    (Cafeteria, 5),
-    (TrashCompactor, 6),
-    (Laboratories, 7),
-    (Playground, 8),
-    (MovieTheater, 9),
-    (Factory, 10),
-    (Reactor, 11),
-    (ChristmasTreeFarm, 12),
+// days from other years go after the `;`, in the same form as the registry
+aoc2025::registry! {
+    generate_cli;
     // command_marker
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_cli() {
+        Cli::command().debug_assert();
+    }
+}
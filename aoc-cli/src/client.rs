@@ -0,0 +1,339 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use ureq::Agent;
+
+pub(crate) const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+// https://www.reddit.com/r/adventofcode/wiki/faqs/automation
+const USER_AGENT: &str = concat!(
+    "github.com/mattcl/aoc2025 aoc-cli/",
+    env!("CARGO_PKG_VERSION"),
+    " by matt@questionable.engineering"
+);
+
+/// A minimal client for the Advent of Code site (or anything pretending to be
+/// it, like a local mock server).
+#[derive(Debug, Clone)]
+pub(crate) struct AocClient {
+    agent: Agent,
+    base_url: String,
+    session: String,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = Agent::config_builder()
+            .user_agent(USER_AGENT)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .http_status_as_error(false)
+            .build()
+            .into();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+        }
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    pub fn input(&self, year: usize, day: usize) -> Result<String> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let mut resp = self
+            .agent
+            .get(&url)
+            .header("Cookie", self.cookie())
+            .call()
+            .with_context(|| format!("Could not fetch {}", url))?;
+
+        let status = resp.status().as_u16();
+        let body = resp
+            .body_mut()
+            .read_to_string()
+            .context("Could not read response body")?;
+
+        match status {
+            200 => Ok(body),
+            404 => bail!("Input for {} day {} is not available (yet?)", year, day),
+            400 | 401 | 500 => bail!(
+                "Server rejected the request ({}), is the session token valid?",
+                status
+            ),
+            _ => bail!("Unexpected response ({}): {}", status, body.trim()),
+        }
+    }
+
+    /// Submit an answer, returning the server's verdict.
+    pub fn answer(&self, year: usize, day: usize, part: u8, answer: &str) -> Result<Verdict> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let level = part.to_string();
+        let mut resp = self
+            .agent
+            .post(&url)
+            .header("Cookie", self.cookie())
+            .send_form([("level", level.as_str()), ("answer", answer)])
+            .with_context(|| format!("Could not submit to {}", url))?;
+
+        let status = resp.status().as_u16();
+        let body = resp
+            .body_mut()
+            .read_to_string()
+            .context("Could not read response body")?;
+
+        match status {
+            200 => Ok(Verdict::from_response(&body)),
+            400 | 401 | 500 => bail!(
+                "Server rejected the request ({}), is the session token valid?",
+                status
+            ),
+            _ => bail!("Unexpected response ({}): {}", status, body.trim()),
+        }
+    }
+}
+
+/// What the server thought of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "verdict")]
+pub(crate) enum Verdict {
+    Correct,
+    TooHigh {
+        wait: u64,
+    },
+    TooLow {
+        wait: u64,
+    },
+    Incorrect {
+        wait: u64,
+    },
+    /// The answer was not checked because we submitted too recently.
+    Cooldown {
+        wait: u64,
+    },
+    /// The part was already solved (or is not unlocked yet).
+    WrongLevel,
+    Unknown,
+}
+
+impl Verdict {
+    /// Make sense of the (html) response to an answer submission.
+    pub fn from_response(body: &str) -> Self {
+        // we only care about the main article, not the rest of the page
+        let text = body
+            .split_once("<article>")
+            .map(|(_, rest)| rest.split_once("</article>").map_or(rest, |(a, _)| a))
+            .unwrap_or(body);
+
+        if text.contains("That's the right answer") {
+            Self::Correct
+        } else if text.contains("That's not the right answer") {
+            let wait = parse_wait(text).unwrap_or(60);
+            if text.contains("your answer is too high") {
+                Self::TooHigh { wait }
+            } else if text.contains("your answer is too low") {
+                Self::TooLow { wait }
+            } else {
+                Self::Incorrect { wait }
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Self::Cooldown {
+                wait: parse_wait(text).unwrap_or(60),
+            }
+        } else if text.contains("You don't seem to be solving the right level") {
+            Self::WrongLevel
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// How long the server wants us to wait before submitting again.
+    pub fn wait(&self) -> u64 {
+        match self {
+            Self::TooHigh { wait }
+            | Self::TooLow { wait }
+            | Self::Incorrect { wait }
+            | Self::Cooldown { wait } => *wait,
+            _ => 0,
+        }
+    }
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::TooHigh { .. } => write!(f, "incorrect (too high)"),
+            Self::TooLow { .. } => write!(f, "incorrect (too low)"),
+            Self::Incorrect { .. } => write!(f, "incorrect"),
+            Self::Cooldown { .. } => write!(f, "not checked, answered too recently"),
+            Self::WrongLevel => write!(f, "not checked, already solved or locked"),
+            Self::Unknown => write!(f, "unknown response"),
+        }
+    }
+}
+
+/// Extract a wait time in seconds from messages like "Please wait one minute
+/// before trying again" or "You have 1m 4s left to wait".
+fn parse_wait(text: &str) -> Option<u64> {
+    if let Some((before, _)) = text.split_once(" left to wait") {
+        let (_, remaining) = before.rsplit_once("You have ")?;
+        let mut total = 0;
+        for part in remaining.split_whitespace() {
+            let (num, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let num: u64 = num.parse().ok()?;
+            total += match unit {
+                "h" => num * 3600,
+                "m" => num * 60,
+                "s" => num,
+                _ => return None,
+            };
+        }
+        return Some(total);
+    }
+
+    let (_, rest) = text.split_once("lease wait ")?;
+    let mut words = rest.split_whitespace();
+    let amount = match words.next()? {
+        "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        other => other.parse().ok()?,
+    };
+
+    match words.next()? {
+        unit if unit.starts_with("minute") => Some(amount * 60),
+        unit if unit.starts_with("second") => Some(amount),
+        _ => None,
+    }
+}
+
+/// A throwaway single-threaded http server for exercising the client.
+#[cfg(test)]
+pub(crate) mod test_server {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc::{self, Receiver},
+        thread,
+    };
+
+    #[derive(Debug, Clone)]
+    pub struct Request {
+        pub request_line: String,
+        pub cookie: Option<String>,
+        pub body: String,
+    }
+
+    /// Serve the given `(status, body)` responses in order, one per
+    /// connection, returning the base url and a channel of the requests
+    /// received.
+    pub fn serve(responses: Vec<(u16, String)>) -> (String, Receiver<Request>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("could not bind");
+        let addr = listener.local_addr().expect("no local addr");
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let Ok((stream, _)) = listener.accept() else {
+                    return;
+                };
+                let mut reader = BufReader::new(stream);
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+
+                let mut cookie = None;
+                let mut len = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end();
+                    if line.is_empty() {
+                        break;
+                    }
+                    let (name, value) = line.split_once(':').unwrap();
+                    match name.to_ascii_lowercase().as_str() {
+                        "cookie" => cookie = Some(value.trim().to_string()),
+                        "content-length" => len = value.trim().parse().unwrap(),
+                        _ => {}
+                    }
+                }
+
+                let mut raw = vec![0; len];
+                reader.read_exact(&mut raw).unwrap();
+
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+
+                let _ = tx.send(Request {
+                    request_line: request_line.trim_end().to_string(),
+                    cookie,
+                    body: String::from_utf8(raw).unwrap(),
+                });
+            }
+        });
+
+        (format!("http://{}", addr), rx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verdicts() {
+        let wrap = |s: &str| format!("<main>\n<article><p>{}</p></article>\n</main>", s);
+
+        assert_eq!(
+            Verdict::from_response(&wrap(
+                "That's the right answer! You are one gold star closer."
+            )),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_response(&wrap(
+                "That's not the right answer; your answer is too high. Please wait one minute before trying again."
+            )),
+            Verdict::TooHigh { wait: 60 }
+        );
+        assert_eq!(
+            Verdict::from_response(&wrap(
+                "That's not the right answer; your answer is too low. Please wait 5 minutes before trying again."
+            )),
+            Verdict::TooLow { wait: 300 }
+        );
+        assert_eq!(
+            Verdict::from_response(&wrap(
+                "That's not the right answer. If you're stuck, try the subreddit. Please wait one minute before trying again."
+            )),
+            Verdict::Incorrect { wait: 60 }
+        );
+        assert_eq!(
+            Verdict::from_response(&wrap(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 4s left to wait."
+            )),
+            Verdict::Cooldown { wait: 64 }
+        );
+        assert_eq!(
+            Verdict::from_response(&wrap(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Verdict::WrongLevel
+        );
+        assert_eq!(Verdict::from_response("<html></html>"), Verdict::Unknown);
+    }
+}
@@ -0,0 +1,471 @@
+use std::{
+    fmt::Display,
+    io::IsTerminal,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+use clap::{Args, Subcommand};
+use serde::Deserialize;
+
+use aoc_plumbing::DEFAULT_YEAR;
+
+use crate::{client::DEFAULT_BASE_URL, workspace::find_workspace_root};
+
+pub(crate) const WORKSPACE_CONFIG: &str = "aoc.toml";
+
+/// How solutions are printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum OutputFormat {
+    #[default]
+    Plain,
+    Json,
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Plain => write!(f, "plain"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// Where the effective value of a setting came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Source {
+    Default,
+    File(PathBuf),
+    Env(&'static str),
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Env(var) => write!(f, "env {}", var),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Setting<T> {
+    pub value: T,
+    pub source: Source,
+}
+
+impl<T> Setting<T> {
+    fn new(value: T) -> Self {
+        Self {
+            value,
+            source: Source::Default,
+        }
+    }
+
+    fn set(&mut self, value: T, source: Source) {
+        self.value = value;
+        self.source = source;
+    }
+}
+
+/// The contents of either the user or the workspace config file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    year: Option<usize>,
+    input_root: Option<PathBuf>,
+    output: Option<OutputFormat>,
+    timing: Option<bool>,
+    colors: Option<bool>,
+    session: Option<String>,
+    base_url: Option<String>,
+}
+
+impl ConfigFile {
+    fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("Could not read {}", path.display()))?;
+        let file =
+            toml::from_str(&raw).with_context(|| format!("Could not parse {}", path.display()))?;
+        Ok(Some(file))
+    }
+}
+
+/// The effective configuration for the cli.
+///
+/// Settings are layered, with later layers taking precedence:
+///
+/// 1. built-in defaults
+/// 2. the user config (e.g. `~/.config/aoc/config.toml`)
+/// 3. `aoc.toml` in the workspace root
+/// 4. environment variables
+///
+/// Flags passed on the command line take precedence over all of these. A
+/// layer that can't be used, like a file that doesn't parse or an env var with
+/// a bad value, is skipped without affecting the others.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Config {
+    /// The year to use when none is given on the command line.
+    pub year: Setting<usize>,
+    /// The directory containing the `day-NNN-*` crates.
+    pub input_root: Setting<PathBuf>,
+    pub output: Setting<OutputFormat>,
+    /// Whether to report how long solving took.
+    pub timing: Setting<bool>,
+    pub colors: Setting<bool>,
+    pub session: Setting<Option<String>>,
+    pub base_url: Setting<String>,
+}
+
+impl Config {
+    /// Load every layer, along with why any of them were skipped.
+    pub fn load() -> (Self, Vec<anyhow::Error>) {
+        let workspace = find_workspace_root().ok();
+        let user = dirs::config_dir().map(|d| d.join("aoc").join("config.toml"));
+
+        Self::load_from(
+            workspace.as_deref(),
+            user.as_deref(),
+            |var| std::env::var(var).ok(),
+            std::io::stdout().is_terminal(),
+        )
+    }
+
+    fn base(workspace: Option<&Path>, is_terminal: bool) -> Self {
+        Self {
+            year: Setting::new(DEFAULT_YEAR),
+            input_root: Setting::new(
+                workspace
+                    .map(Path::to_path_buf)
+                    .unwrap_or_else(|| PathBuf::from(".")),
+            ),
+            output: Setting::new(OutputFormat::Plain),
+            timing: Setting::new(false),
+            colors: Setting::new(is_terminal),
+            session: Setting::new(None),
+            base_url: Setting::new(DEFAULT_BASE_URL.to_string()),
+        }
+    }
+
+    pub(crate) fn load_from<F>(
+        workspace: Option<&Path>,
+        user_file: Option<&Path>,
+        env: F,
+        is_terminal: bool,
+    ) -> (Self, Vec<anyhow::Error>)
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let mut config = Self::base(workspace, is_terminal);
+        let mut skipped = Vec::default();
+
+        if let Some(path) = user_file {
+            skipped.extend(config.apply_file(path).err());
+        }
+
+        if let Some(root) = workspace {
+            skipped.extend(config.apply_file(&root.join(WORKSPACE_CONFIG)).err());
+        }
+
+        config.apply_env(env, &mut skipped);
+
+        (config, skipped)
+    }
+
+    fn apply_file(&mut self, path: &Path) -> Result<()> {
+        let Some(file) = ConfigFile::load(path)? else {
+            return Ok(());
+        };
+        let source = || Source::File(path.to_path_buf());
+
+        if let Some(year) = file.year {
+            self.year.set(year, source());
+        }
+        if let Some(input_root) = file.input_root {
+            // relative paths are relative to the file that specified them
+            let base = path.parent().unwrap_or(Path::new("."));
+            self.input_root.set(base.join(input_root), source());
+        }
+        if let Some(output) = file.output {
+            self.output.set(output, source());
+        }
+        if let Some(timing) = file.timing {
+            self.timing.set(timing, source());
+        }
+        if let Some(colors) = file.colors {
+            self.colors.set(colors, source());
+        }
+        if let Some(session) = file.session {
+            self.session.set(Some(session), source());
+        }
+        if let Some(base_url) = file.base_url {
+            self.base_url.set(base_url, source());
+        }
+
+        Ok(())
+    }
+
+    /// Apply each env var that's set, adding any with bad values to `skipped`.
+    fn apply_env<F>(&mut self, env: F, skipped: &mut Vec<anyhow::Error>)
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        if let Some(raw) = env("AOC_YEAR") {
+            match raw
+                .trim()
+                .parse()
+                .with_context(|| format!("Invalid value for AOC_YEAR: '{}'", raw))
+            {
+                Ok(year) => self.year.set(year, Source::Env("AOC_YEAR")),
+                Err(e) => skipped.push(e),
+            }
+        }
+
+        if let Some(root) = env("AOC_INPUT_ROOT") {
+            self.input_root
+                .set(PathBuf::from(root), Source::Env("AOC_INPUT_ROOT"));
+        }
+
+        for var in ["AOC_OUTPUT_JSON", "AOC_JSON"] {
+            if let Some(raw) = env(var) {
+                match parse_bool(var, &raw) {
+                    Ok(true) => self.output.set(OutputFormat::Json, Source::Env(var)),
+                    Ok(false) => self.output.set(OutputFormat::Plain, Source::Env(var)),
+                    Err(e) => skipped.push(e),
+                }
+            }
+        }
+
+        if let Some(raw) = env("AOC_TIMING") {
+            match parse_bool("AOC_TIMING", &raw) {
+                Ok(timing) => self.timing.set(timing, Source::Env("AOC_TIMING")),
+                Err(e) => skipped.push(e),
+            }
+        }
+
+        // https://no-color.org
+        if env("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+            self.colors.set(false, Source::Env("NO_COLOR"));
+        }
+        if let Some(raw) = env("AOC_COLORS") {
+            match parse_bool("AOC_COLORS", &raw) {
+                Ok(colors) => self.colors.set(colors, Source::Env("AOC_COLORS")),
+                Err(e) => skipped.push(e),
+            }
+        }
+
+        if let Some(session) = env("AOC_SESSION") {
+            self.session.set(Some(session), Source::Env("AOC_SESSION"));
+        }
+
+        if let Some(base_url) = env("AOC_BASE_URL") {
+            self.base_url.set(base_url, Source::Env("AOC_BASE_URL"));
+        }
+    }
+
+    /// The session token, from the given override or the config.
+    pub fn session(&self, cli: Option<&String>) -> Result<String> {
+        match cli.or(self.session.value.as_ref()) {
+            Some(session) => Ok(session.clone()),
+            None => bail!(
+                "A session token is required, set `session` in {} or AOC_SESSION",
+                WORKSPACE_CONFIG
+            ),
+        }
+    }
+
+    /// The year, from the given override or the config.
+    pub fn year(&self, cli: Option<usize>) -> usize {
+        cli.unwrap_or(self.year.value)
+    }
+
+    /// The input root, from the given override or the config.
+    pub fn input_root(&self, cli: Option<&PathBuf>) -> PathBuf {
+        cli.unwrap_or(&self.input_root.value).clone()
+    }
+
+    /// The base url, from the given override or the config.
+    pub fn base_url<'a>(&'a self, cli: Option<&'a String>) -> &'a str {
+        cli.unwrap_or(&self.base_url.value)
+    }
+
+    pub fn json(&self, cli: bool) -> bool {
+        cli || self.output.value == OutputFormat::Json
+    }
+}
+
+fn parse_bool(var: &str, raw: &str) -> Result<bool> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Ok(true),
+        "" | "0" | "false" | "no" | "off" => Ok(false),
+        _ => bail!(
+            "Invalid value for {}: '{}', expected true or false",
+            var,
+            raw
+        ),
+    }
+}
+
+/// Inspect the cli configuration.
+#[derive(Args)]
+pub(crate) struct ConfigCmd {
+    #[command(subcommand)]
+    command: ConfigCommands,
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print the effective configuration and where each value came from.
+    Show,
+}
+
+impl ConfigCmd {
+    pub fn run(&self, config: &Config) -> Result<()> {
+        match self.command {
+            ConfigCommands::Show => {
+                let session = config.session.value.as_ref().map(|_| "********");
+                let rows = [
+                    ("year", config.year.value.to_string(), &config.year.source),
+                    (
+                        "input_root",
+                        config.input_root.value.display().to_string(),
+                        &config.input_root.source,
+                    ),
+                    (
+                        "output",
+                        config.output.value.to_string(),
+                        &config.output.source,
+                    ),
+                    (
+                        "timing",
+                        config.timing.value.to_string(),
+                        &config.timing.source,
+                    ),
+                    (
+                        "colors",
+                        config.colors.value.to_string(),
+                        &config.colors.source,
+                    ),
+                    (
+                        "session",
+                        session.unwrap_or("<unset>").to_string(),
+                        &config.session.source,
+                    ),
+                    (
+                        "base_url",
+                        config.base_url.value.clone(),
+                        &config.base_url.source,
+                    ),
+                ];
+
+                let width = rows.iter().map(|(_, v, _)| v.len()).max().unwrap_or(0);
+                for (key, value, source) in rows {
+                    println!("{:<10} = {:<width$}  ({})", key, value, source);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn layering() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = dir.path().join("ws");
+        std::fs::create_dir(&workspace).unwrap();
+        let user = dir.path().join("user.toml");
+
+        std::fs::write(
+            &user,
+            "year = 2024\ntiming = true\ncolors = true\nsession = \"from-user\"\noutput = \"json\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            workspace.join(WORKSPACE_CONFIG),
+            "input_root = \"inputs\"\ncolors = false\n",
+        )
+        .unwrap();
+
+        let env: HashMap<&str, &str> = [
+            ("AOC_JSON", "false"),
+            ("AOC_SESSION", "from-env"),
+            ("AOC_YEAR", "2023"),
+        ]
+        .into_iter()
+        .collect();
+
+        let (config, skipped) = Config::load_from(
+            Some(&workspace),
+            Some(&user),
+            |var| env.get(var).map(|v| v.to_string()),
+            true,
+        );
+        assert!(skipped.is_empty());
+
+        assert_eq!(config.year.value, 2023);
+        assert_eq!(config.input_root.value, workspace.join("inputs"));
+        assert_eq!(
+            config.input_root.source,
+            Source::File(workspace.join(WORKSPACE_CONFIG))
+        );
+        assert!(config.timing.value);
+        assert_eq!(config.timing.source, Source::File(user.clone()));
+        assert!(!config.colors.value);
+        assert_eq!(config.output.value, OutputFormat::Plain);
+        assert_eq!(config.output.source, Source::Env("AOC_JSON"));
+        assert_eq!(config.session.value.as_deref(), Some("from-env"));
+        assert_eq!(config.base_url.source, Source::Default);
+    }
+
+    #[test]
+    fn skips_garbage() {
+        let dir = tempfile::tempdir().unwrap();
+        let user = dir.path().join("user.toml");
+        std::fs::write(&user, "year = 2024\ninput_root = \"inputs\"\n").unwrap();
+        std::fs::write(dir.path().join(WORKSPACE_CONFIG), "colour = true\n").unwrap();
+
+        let env: HashMap<&str, &str> = [
+            ("AOC_TIMING", "maybe"),
+            ("AOC_JSON", "true"),
+            ("AOC_SESSION", "from-env"),
+        ]
+        .into_iter()
+        .collect();
+
+        let (config, skipped) = Config::load_from(
+            Some(dir.path()),
+            Some(&user),
+            |var| env.get(var).map(|v| v.to_string()),
+            false,
+        );
+
+        // just the bad file and the bad var are skipped
+        assert_eq!(skipped.len(), 2);
+        assert!(format!("{:#}", skipped[0]).contains(WORKSPACE_CONFIG));
+        assert!(format!("{:#}", skipped[1]).contains("AOC_TIMING"));
+        assert_eq!(config.year.value, 2024);
+        assert_eq!(config.input_root.value, dir.path().join("inputs"));
+        assert_eq!(config.colors.source, Source::Default);
+        assert_eq!(config.timing.source, Source::Default);
+        assert_eq!(config.output.value, OutputFormat::Json);
+        assert_eq!(config.session.value.as_deref(), Some("from-env"));
+
+        // with nothing to load, it's just the defaults
+        let (config, skipped) = Config::load_from(None, None, |_| None, false);
+        assert!(skipped.is_empty());
+        assert_eq!(config, Config::base(None, false));
+    }
+}
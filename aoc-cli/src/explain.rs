@@ -0,0 +1,140 @@
+use std::{
+    fmt::Write as _,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+};
+
+use anyhow::{Context, Result, bail};
+use aoc_plumbing::{
+    DynSolution,
+    trace::{self, Event},
+};
+use clap::{Args, ValueEnum};
+
+use crate::{
+    cli::{print_solution, solve_dyn},
+    config::Config,
+    style::Painter,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum Format {
+    /// One json object per line.
+    Jsonl,
+    /// One `event key=value ...` line per event.
+    Text,
+}
+
+/// Solve a day, dumping the events the solver emitted along the way.
+///
+/// Events are printed even if solving fails, which is usually when they're
+/// most useful. In jsonl, the answers are the final line, as a `solution`
+/// event.
+#[derive(Args)]
+pub(crate) struct Explain {
+    /// The day to solve.
+    day: usize,
+
+    /// The path to the input to solve.
+    input: PathBuf,
+
+    /// The year of the day to solve.
+    ///
+    /// Defaults to the `year` from the config.
+    #[clap(short, long)]
+    year: Option<usize>,
+
+    /// The output format.
+    #[clap(short, long, value_enum, default_value_t = Format::Jsonl)]
+    format: Format,
+
+    /// Only print events with these names.
+    #[clap(short, long)]
+    event: Vec<String>,
+}
+
+impl Explain {
+    pub fn run(&self, config: &Config) -> Result<()> {
+        let year = config.year(self.year);
+        let input = std::fs::read_to_string(&self.input).context("Could not read input file")?;
+
+        let (solution, events) = trace::collect(|| solve_dyn(year, self.day, &input));
+        let Some(solution) = solution else {
+            bail!("{} day {} is not implemented", year, self.day);
+        };
+
+        let painter = Painter::new(config.colors.value);
+        match self.write_events(&events, &painter) {
+            // the output is often piped into `head` or similar, so stop quietly
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+            res => res?,
+        }
+
+        let solution = solution?;
+        match self.format {
+            Format::Jsonl => println!("{}", serde_json::to_string(&solution_event(&solution))?),
+            Format::Text => print_solution(&solution.part_one, &solution.part_two, &painter),
+        }
+
+        Ok(())
+    }
+
+    /// There can be a lot of events, so these are buffered.
+    fn write_events(&self, events: &[Event], painter: &Painter) -> io::Result<()> {
+        let mut out = BufWriter::new(io::stdout().lock());
+        for event in events
+            .iter()
+            .filter(|e| self.event.is_empty() || self.event.iter().any(|n| n == e.event))
+        {
+            match self.format {
+                Format::Jsonl => writeln!(out, "{}", serde_json::to_string(event)?)?,
+                Format::Text => writeln!(out, "{}", text(event, painter))?,
+            }
+        }
+        out.flush()
+    }
+}
+
+fn solution_event(solution: &DynSolution) -> Event {
+    Event {
+        event: "solution",
+        fields: vec![
+            ("part_one", trace::to_value(&solution.part_one)),
+            ("part_two", trace::to_value(&solution.part_two)),
+        ],
+    }
+}
+
+fn text(event: &Event, painter: &Painter) -> String {
+    let mut out = painter.bold(event.event);
+    for (key, value) in &event.fields {
+        // writing to a string can't fail
+        let _ = write!(out, " {}={}", painter.dim(key), value);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collects_events() {
+        let (solution, events) = trace::collect(|| solve_dyn(2025, 1, "L68\nL30\nR48"));
+        let solution = solution.unwrap().unwrap();
+        assert_eq!(solution.part_one.to_string(), "1");
+
+        assert_eq!(events.len(), 3);
+        assert!(events.iter().all(|e| e.event == "rotation"));
+        assert_eq!(
+            text(&events[2], &Painter::new(false)),
+            "rotation direction=\"R\" distance=48 position=0 zeros=1 passes=2"
+        );
+
+        let event = solution_event(&solution);
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"event":"solution","part_one":1,"part_two":2}"#
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Args;
+
+use crate::{client::AocClient, config::Config, workspace::input_path};
+
+/// Download the input for a given day.
+///
+/// The input is saved to the conventional `day-NNN-*/input.txt` location. An
+/// existing, non-empty input is never downloaded again.
+#[derive(Args)]
+pub(crate) struct Fetch {
+    /// The day to fetch.
+    day: usize,
+
+    /// The year of the day to fetch.
+    ///
+    /// Defaults to the `year` from the config.
+    #[clap(short, long)]
+    year: Option<usize>,
+
+    /// The session token (the value of the `session` cookie).
+    ///
+    /// This may be specified instead by setting `session` in the config, or
+    /// by setting the `AOC_SESSION` env var.
+    #[clap(long)]
+    session: Option<String>,
+
+    /// The base url of the Advent of Code site.
+    ///
+    /// This is mainly useful for pointing at a local mock server.
+    ///
+    /// Defaults to the `base_url` from the config.
+    #[clap(long)]
+    base_url: Option<String>,
+
+    /// The directory containing the `day-NNN-*` crates.
+    ///
+    /// Defaults to the `input_root` from the config.
+    #[clap(long)]
+    root: Option<PathBuf>,
+}
+
+impl Fetch {
+    pub fn run(&self, config: &Config) -> Result<()> {
+        let root = config.input_root(self.root.as_ref());
+        let year = config.year(self.year);
+
+        let path = input_path(&root, year, self.day)?;
+        if is_cached(&path) {
+            println!("{} already exists, not fetching", path.display());
+            return Ok(());
+        }
+
+        let session = config.session(self.session.as_ref())?;
+        let client = AocClient::new(config.base_url(self.base_url.as_ref()), &session);
+        fetch_to(&client, year, self.day, &path)?;
+        println!(
+            "saved input for {} day {} to {}",
+            year,
+            self.day,
+            path.display()
+        );
+
+        Ok(())
+    }
+}
+
+/// The template creates an empty input, so we only consider non-empty files as
+/// having been fetched.
+fn is_cached(path: &Path) -> bool {
+    path.metadata().map(|m| m.len() > 0).unwrap_or(false)
+}
+
+fn fetch_to(client: &AocClient, year: usize, day: usize, path: &Path) -> Result<()> {
+    let input = client.input(year, day)?;
+    std::fs::write(path, input).with_context(|| format!("Could not write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::test_server;
+
+    use super::*;
+
+    #[test]
+    fn fetches_once() {
+        let root = tempfile::tempdir().unwrap();
+        let day_dir = root.path().join("day-003-lobby");
+        std::fs::create_dir(&day_dir).unwrap();
+        std::fs::write(day_dir.join("input.txt"), "").unwrap();
+
+        let (base_url, requests) = test_server::serve(vec![(200, "1234\n5678\n".into())]);
+
+        let (config, _) = Config::load_from(None, None, |_| None, false);
+        let cmd = Fetch {
+            day: 3,
+            year: None,
+            session: Some("abc".into()),
+            base_url: Some(base_url),
+            root: Some(root.path().to_path_buf()),
+        };
+
+        cmd.run(&config).unwrap();
+        let req = requests.recv().unwrap();
+        assert_eq!(req.request_line, "GET /2025/day/3/input HTTP/1.1");
+        assert_eq!(req.cookie.as_deref(), Some("session=abc"));
+        assert_eq!(
+            std::fs::read_to_string(day_dir.join("input.txt")).unwrap(),
+            "1234\n5678\n"
+        );
+
+        // the server would refuse a second connection, so this must not hit it
+        cmd.run(&config).unwrap();
+        assert!(requests.try_recv().is_err());
+    }
+
+    #[test]
+    fn other_years() {
+        let root = tempfile::tempdir().unwrap();
+        let day_dir = root.path().join("2024").join("day-003-mull-it-over");
+        std::fs::create_dir_all(&day_dir).unwrap();
+
+        let (base_url, requests) = test_server::serve(vec![(200, "mul(2,4)\n".into())]);
+
+        let (config, _) = Config::load_from(None, None, |_| None, false);
+        let cmd = Fetch {
+            day: 3,
+            year: Some(2024),
+            session: Some("abc".into()),
+            base_url: Some(base_url),
+            root: Some(root.path().to_path_buf()),
+        };
+
+        cmd.run(&config).unwrap();
+        let req = requests.recv().unwrap();
+        assert_eq!(req.request_line, "GET /2024/day/3/input HTTP/1.1");
+        assert_eq!(
+            std::fs::read_to_string(day_dir.join("input.txt")).unwrap(),
+            "mul(2,4)\n"
+        );
+    }
+
+    #[test]
+    fn missing_input() {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join("input.txt");
+        let (base_url, _requests) = test_server::serve(vec![(404, "not found".into())]);
+
+        let client = AocClient::new(&base_url, "abc");
+        assert!(fetch_to(&client, 2025, 3, &path).is_err());
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,267 @@
+use std::{
+    collections::BTreeMap,
+    num::ParseIntError,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::client::Verdict;
+
+pub(crate) const HISTORY_FILE: &str = ".aoc/history.json";
+
+/// A single submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct Attempt {
+    pub answer: String,
+    #[serde(flatten)]
+    pub verdict: Verdict,
+    /// Seconds since the epoch.
+    pub at: u64,
+}
+
+/// Every attempt for a single part of a single day.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct PartHistory {
+    pub attempts: Vec<Attempt>,
+    /// Seconds since the epoch before which we should not submit again.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_until: Option<u64>,
+}
+
+/// A numeric answer, which may only fit in an `i128` or only in a `u128`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Numeric {
+    // declared first so every negative answer orders below the rest
+    Negative(i128),
+    NonNegative(u128),
+}
+
+impl FromStr for Numeric {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse::<u128>() {
+            Ok(value) => Ok(Self::NonNegative(value)),
+            Err(_) => s.parse::<i128>().map(|value| match u128::try_from(value) {
+                Ok(value) => Self::NonNegative(value),
+                Err(_) => Self::Negative(value),
+            }),
+        }
+    }
+}
+
+impl std::fmt::Display for Numeric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Negative(value) => write!(f, "{}", value),
+            Self::NonNegative(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// Why we won't submit an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Refusal {
+    AlreadySolved(String),
+    KnownWrong,
+    TooHigh(String),
+    TooLow(String),
+    Cooldown(u64),
+}
+
+impl std::fmt::Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AlreadySolved(answer) => write!(f, "already solved with {}", answer),
+            Self::KnownWrong => write!(f, "this answer was already rejected"),
+            Self::TooHigh(bound) => write!(f, "{} was already too high", bound),
+            Self::TooLow(bound) => write!(f, "{} was already too low", bound),
+            Self::Cooldown(secs) => write!(f, "need to wait another {}s", secs),
+        }
+    }
+}
+
+impl PartHistory {
+    pub fn solved(&self) -> Option<&Attempt> {
+        self.attempts.iter().find(|a| a.verdict == Verdict::Correct)
+    }
+
+    /// The smallest answer known to be too high.
+    pub fn upper_bound(&self) -> Option<Numeric> {
+        self.attempts
+            .iter()
+            .filter(|a| matches!(a.verdict, Verdict::TooHigh { .. }))
+            .filter_map(|a| a.answer.parse().ok())
+            .min()
+    }
+
+    /// The largest answer known to be too low.
+    pub fn lower_bound(&self) -> Option<Numeric> {
+        self.attempts
+            .iter()
+            .filter(|a| matches!(a.verdict, Verdict::TooLow { .. }))
+            .filter_map(|a| a.answer.parse().ok())
+            .max()
+    }
+
+    /// Check whether it makes sense to submit `answer` at time `now`.
+    pub fn check(&self, answer: &str, now: u64) -> Result<(), Refusal> {
+        if let Some(solved) = self.solved() {
+            return Err(Refusal::AlreadySolved(solved.answer.clone()));
+        }
+
+        let rejected = self.attempts.iter().any(|a| {
+            a.answer == answer
+                && matches!(
+                    a.verdict,
+                    Verdict::TooHigh { .. } | Verdict::TooLow { .. } | Verdict::Incorrect { .. }
+                )
+        });
+        if rejected {
+            return Err(Refusal::KnownWrong);
+        }
+
+        if let Ok(value) = answer.parse::<Numeric>() {
+            if let Some(upper) = self.upper_bound().filter(|u| value >= *u) {
+                return Err(Refusal::TooHigh(upper.to_string()));
+            }
+            if let Some(lower) = self.lower_bound().filter(|l| value <= *l) {
+                return Err(Refusal::TooLow(lower.to_string()));
+            }
+        }
+
+        if let Some(wait_until) = self.wait_until.filter(|w| *w > now) {
+            return Err(Refusal::Cooldown(wait_until - now));
+        }
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, answer: &str, verdict: Verdict, now: u64) {
+        self.attempts.push(Attempt {
+            answer: answer.to_string(),
+            verdict,
+            at: now,
+        });
+
+        let wait = verdict.wait();
+        self.wait_until = if wait > 0 { Some(now + wait) } else { None };
+    }
+}
+
+/// The local record of everything we've submitted, keyed by `year-day-part`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct History {
+    #[serde(skip)]
+    path: PathBuf,
+    parts: BTreeMap<String, PartHistory>,
+}
+
+impl History {
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(HISTORY_FILE);
+        let mut history: Self = if path.exists() {
+            let raw = std::fs::read_to_string(&path)
+                .with_context(|| format!("Could not read {}", path.display()))?;
+            serde_json::from_str(&raw)
+                .with_context(|| format!("Could not parse {}", path.display()))?
+        } else {
+            Self::default()
+        };
+
+        history.path = path;
+        Ok(history)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Could not create {}", parent.display()))?;
+        }
+        std::fs::write(&self.path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Could not write {}", self.path.display()))
+    }
+
+    pub fn part_mut(&mut self, year: usize, day: usize, part: u8) -> &mut PartHistory {
+        self.parts
+            .entry(format!("{}-{:02}-{}", year, day, part))
+            .or_default()
+    }
+}
+
+pub(crate) fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refusals() {
+        let mut part = PartHistory::default();
+        assert_eq!(part.check("100", 0), Ok(()));
+
+        part.record("100", Verdict::TooHigh { wait: 60 }, 0);
+        assert_eq!(part.check("50", 30), Err(Refusal::Cooldown(30)));
+        assert_eq!(part.check("100", 60), Err(Refusal::KnownWrong));
+        assert_eq!(part.check("150", 60), Err(Refusal::TooHigh("100".into())));
+        assert_eq!(part.check("50", 60), Ok(()));
+
+        part.record("20", Verdict::TooLow { wait: 60 }, 60);
+        assert_eq!(part.check("10", 120), Err(Refusal::TooLow("20".into())));
+        assert_eq!(part.check("abc", 120), Ok(()));
+
+        part.record("abc", Verdict::Incorrect { wait: 60 }, 120);
+        assert_eq!(part.check("abc", 180), Err(Refusal::KnownWrong));
+
+        part.record("50", Verdict::Correct, 180);
+        assert_eq!(
+            part.check("60", 240),
+            Err(Refusal::AlreadySolved("50".into()))
+        );
+    }
+
+    #[test]
+    fn wide_bounds() {
+        let huge = (i128::MAX as u128 + 1).to_string();
+        let huger = u128::MAX.to_string();
+
+        let mut part = PartHistory::default();
+        part.record(&huge, Verdict::TooHigh { wait: 0 }, 0);
+        assert_eq!(part.check(&huger, 0), Err(Refusal::TooHigh(huge.clone())));
+        assert_eq!(part.check("5", 0), Ok(()));
+
+        part.record("-10", Verdict::TooLow { wait: 0 }, 0);
+        assert_eq!(part.check("-20", 0), Err(Refusal::TooLow("-10".into())));
+        assert_eq!(part.check("-0", 0), Ok(()));
+        assert_eq!(part.check(&(i128::MAX as u128).to_string(), 0), Ok(()));
+
+        assert!("-0".parse::<Numeric>().unwrap() > "-1".parse().unwrap());
+        assert_eq!("-0".parse::<Numeric>(), Ok(Numeric::NonNegative(0)));
+    }
+
+    #[test]
+    fn round_trip() {
+        let root = tempfile::tempdir().unwrap();
+        let mut history = History::load(root.path()).unwrap();
+        history
+            .part_mut(2025, 3, 1)
+            .record("1234", Verdict::TooLow { wait: 60 }, 10);
+        history.save().unwrap();
+
+        let mut loaded = History::load(root.path()).unwrap();
+        assert_eq!(loaded, history);
+        assert_eq!(
+            loaded.part_mut(2025, 3, 1).lower_bound(),
+            Some(Numeric::NonNegative(1234))
+        );
+    }
+}
@@ -2,9 +2,48 @@
 // #[global_allocator]
 // static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
+#[cfg(not(feature = "lite"))]
+mod batch;
+
 #[cfg(not(feature = "lite"))]
 mod cli;
 
+#[cfg(not(feature = "lite"))]
+mod client;
+
+#[cfg(not(feature = "lite"))]
+mod config;
+
+#[cfg(all(feature = "trace", not(feature = "lite")))]
+mod explain;
+
+#[cfg(not(feature = "lite"))]
+mod fetch;
+
+#[cfg(not(feature = "lite"))]
+mod history;
+
+#[cfg(not(feature = "lite"))]
+mod new;
+
+#[cfg(not(feature = "lite"))]
+mod serve;
+
+#[cfg(not(feature = "lite"))]
+mod style;
+
+#[cfg(not(feature = "lite"))]
+mod submit;
+
+#[cfg(not(feature = "lite"))]
+mod viz;
+
+#[cfg(not(feature = "lite"))]
+mod watch;
+
+#[cfg(not(feature = "lite"))]
+mod workspace;
+
 #[cfg(feature = "lite")]
 mod fast_cli;
 
@@ -0,0 +1,548 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+use aoc_plumbing::DEFAULT_YEAR;
+use clap::Args;
+
+use crate::{
+    config::Config,
+    workspace::{find_day_dir, find_workspace_root},
+};
+
+const TEMPLATE_CARGO: &str = include_str!("../../template/Cargo.toml");
+const TEMPLATE_README: &str = include_str!("../../template/README.md");
+const TEMPLATE_INPUT: &str = include_str!("../../template/input.txt");
+const TEMPLATE_LIB: &str = include_str!("../../template/src/lib.rs");
+
+const IMPORT_MARKER: &str = "// import_marker";
+const COMMAND_MARKER: &str = "// command_marker";
+const BENCH_MARKER: &str = "// bench_marker";
+const FACADE_MARKER: &str = "// facade_marker";
+const FEATURE_MARKER: &str = "# feature_marker";
+const ALL_MARKER: &str = "# all_marker";
+
+/// Generate the boilerplate for a new day's problem.
+///
+/// This creates `day-NNN-<name>` from the embedded template (in a `<year>`
+/// subdirectory for years other than the default) and registers the new crate
+/// with the benchmarks and the `aoc2025` registry, which the cli and the C ABI
+/// are built from. Other years are registered with the cli directly.
+/// Registrations that already exist are left alone, so this is safe to re-run,
+/// but an existing crate for the given day will never be overwritten.
+#[derive(Args)]
+pub(crate) struct New {
+    /// The day of the new problem.
+    day: usize,
+
+    /// The kebab-case name of the problem (e.g. `secret-entrance`).
+    name: String,
+
+    /// The year of the new problem.
+    ///
+    /// Defaults to the `year` from the config.
+    #[clap(short, long)]
+    year: Option<usize>,
+
+    /// The workspace root.
+    ///
+    /// Defaults to the nearest ancestor of the current directory containing a
+    /// workspace `Cargo.toml`.
+    #[clap(long)]
+    root: Option<PathBuf>,
+
+    /// Print what would change without touching anything.
+    #[clap(long)]
+    dry_run: bool,
+}
+
+impl New {
+    pub fn run(&self, config: &Config) -> Result<()> {
+        let root = match self.root {
+            Some(ref root) => root.clone(),
+            None => find_workspace_root()?,
+        };
+
+        let names = Names::new(config.year(self.year), self.day, &self.name)?;
+        let changes = plan(&root, &names)?;
+
+        if changes.is_empty() {
+            println!("{} is already set up, nothing to do", names.rel_dir);
+            return Ok(());
+        }
+
+        for change in changes.iter() {
+            if self.dry_run {
+                println!("would {}", change);
+            } else {
+                change.apply()?;
+                println!("{}", change);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The various spellings of a problem name needed by the template and the
+/// registrations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Names {
+    year: usize,
+    day: usize,
+    /// `foo-bar`
+    project: String,
+    /// `foo_bar`
+    crate_ident: String,
+    /// `FooBar`
+    struct_name: String,
+    /// `foo bar`
+    title: String,
+    /// `Foo Bar`
+    title_case: String,
+    /// `day-001-foo-bar`
+    dir: String,
+    /// `day-001-foo-bar`, or `2024/day-001-foo-bar` for non-default years
+    rel_dir: String,
+}
+
+impl Names {
+    fn new(year: usize, day: usize, name: &str) -> Result<Self> {
+        if day == 0 || day > 25 {
+            bail!("day must be between 1 and 25, got {}", day);
+        }
+
+        let valid = name.split('-').all(|w| {
+            !w.is_empty()
+                && w.bytes()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+        }) && name.as_bytes()[0].is_ascii_lowercase();
+
+        if !valid {
+            bail!(
+                "invalid name '{}': expected lowercase kebab-case like 'secret-entrance'",
+                name
+            );
+        }
+
+        let words: Vec<&str> = name.split('-').collect();
+        let capitalized: Vec<String> = words
+            .iter()
+            .map(|w| {
+                let (first, rest) = w.split_at(1);
+                format!("{}{}", first.to_ascii_uppercase(), rest)
+            })
+            .collect();
+
+        let dir = format!("day-{:03}-{}", day, name);
+        let rel_dir = if year == DEFAULT_YEAR {
+            dir.clone()
+        } else {
+            format!("{}/{}", year, dir)
+        };
+
+        Ok(Self {
+            year,
+            day,
+            project: name.to_string(),
+            crate_ident: name.replace('-', "_"),
+            struct_name: capitalized.concat(),
+            title: words.join(" "),
+            title_case: capitalized.join(" "),
+            dir,
+            rel_dir,
+        })
+    }
+
+    /// The name of the benchmark function for this problem.
+    fn bench_ident(&self) -> String {
+        if self.year == DEFAULT_YEAR {
+            format!("day_{:03}", self.day)
+        } else {
+            format!("day_{}_{:03}", self.year, self.day)
+        }
+    }
+
+    /// The relative path from the crate back to the workspace root.
+    fn workspace_path(&self) -> String {
+        vec![".."; self.rel_dir.split('/').count()].join("/")
+    }
+
+    fn render(&self, template: &str) -> String {
+        template
+            .replace("{{workspace}}", &self.workspace_path())
+            .replace("{{project-name}}", &self.project)
+            .replace("{{struct_name}}", &self.struct_name)
+            .replace("{{title_case}}", &self.title_case)
+            .replace("{{title}}", &self.title)
+            .replace("{{year}}", &self.year.to_string())
+            .replace("{{day}}", &self.day.to_string())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Change {
+    Create { path: PathBuf, contents: String },
+    Update { path: PathBuf, contents: String },
+}
+
+impl Change {
+    fn apply(&self) -> Result<()> {
+        match self {
+            Self::Create { path, contents } => {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)
+                        .with_context(|| format!("Could not create {}", parent.display()))?;
+                }
+                fs::write(path, contents)
+                    .with_context(|| format!("Could not write {}", path.display()))
+            }
+            Self::Update { path, contents } => fs::write(path, contents)
+                .with_context(|| format!("Could not write {}", path.display())),
+        }
+    }
+}
+
+impl std::fmt::Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Create { path, .. } => write!(f, "create {}", path.display()),
+            Self::Update { path, .. } => write!(f, "update {}", path.display()),
+        }
+    }
+}
+
+/// Determine every file that needs to be created or modified for the given
+/// problem, without modifying anything.
+fn plan(root: &Path, names: &Names) -> Result<Vec<Change>> {
+    let mut changes = Vec::default();
+
+    let crate_exists = match find_day_dir(root, names.year, names.day)? {
+        Some(existing) if !existing.ends_with(&names.dir) => bail!(
+            "{} day {} already exists as {}, refusing to overwrite it",
+            names.year,
+            names.day,
+            existing.display()
+        ),
+        Some(_) => true,
+        None => false,
+    };
+
+    if !crate_exists {
+        let dir = root.join(&names.rel_dir);
+        for (rel, template) in [
+            ("Cargo.toml", TEMPLATE_CARGO),
+            ("README.md", TEMPLATE_README),
+            ("input.txt", TEMPLATE_INPUT),
+            ("src/lib.rs", TEMPLATE_LIB),
+        ] {
+            changes.push(Change::Create {
+                path: dir.join(rel),
+                contents: names.render(template),
+            });
+        }
+    }
+
+    let dep = format!("{} = {{ path = \"../{}\" }}", names.project, names.rel_dir);
+    let import = format!("use {}::{};", names.crate_ident, names.struct_name);
+    let feature = format!("day-{:03}", names.day);
+    let entry = format!(
+        "({}, {}, \"{}\", {}, {}),",
+        names.struct_name, names.crate_ident, feature, names.year, names.day
+    );
+    let bench = format!(
+        "(\n    {},\n    \"../{}/input.txt\",\n    {},\n    \"Part 1\",\n    \"Part 2\"\n),",
+        names.bench_ident(),
+        names.rel_dir,
+        names.struct_name
+    );
+
+    update(&mut changes, &root.join("Cargo.toml"), |s| {
+        register_member(s, &names.rel_dir)
+    })?;
+    // the registry only holds the default year
+    if names.year == DEFAULT_YEAR {
+        let optional_dep = format!(
+            "{} = {{ path = \"../{}\", optional = true }}",
+            names.project, names.rel_dir
+        );
+
+        update(&mut changes, &root.join("aoc2025/Cargo.toml"), |s| {
+            let s = insert_before_marker(s, ALL_MARKER, &format!("\"{}\",", feature))?;
+            let s = insert_before_marker(
+                &s,
+                FEATURE_MARKER,
+                &format!("{} = [\"dep:{}\"]", feature, names.project),
+            )?;
+            append_dependency(&s, &names.project, &optional_dep)
+        })?;
+        update(&mut changes, &root.join("aoc2025/src/lib.rs"), |s| {
+            insert_before_marker(s, FACADE_MARKER, &entry)
+        })?;
+    } else {
+        update(&mut changes, &root.join("aoc-cli/Cargo.toml"), |s| {
+            append_dependency(s, &names.project, &dep)
+        })?;
+        for cli in ["aoc-cli/src/cli.rs", "aoc-cli/src/fast_cli.rs"] {
+            update(&mut changes, &root.join(cli), |s| {
+                let s = insert_before_marker(s, IMPORT_MARKER, &import)?;
+                insert_before_marker(&s, COMMAND_MARKER, &entry)
+            })?;
+        }
+    }
+    update(
+        &mut changes,
+        &root.join("aoc-benchmarking/Cargo.toml"),
+        |s| append_dependency(s, &names.project, &dep),
+    )?;
+    update(
+        &mut changes,
+        &root.join("aoc-benchmarking/benches/bench_main.rs"),
+        |s| {
+            let s = insert_before_marker(s, IMPORT_MARKER, &import)?;
+            insert_before_marker(&s, BENCH_MARKER, &bench)
+        },
+    )?;
+
+    Ok(changes)
+}
+
+fn update<F>(changes: &mut Vec<Change>, path: &Path, f: F) -> Result<()>
+where
+    F: FnOnce(&str) -> Result<String>,
+{
+    let original =
+        fs::read_to_string(path).with_context(|| format!("Could not read {}", path.display()))?;
+    let contents = f(&original).with_context(|| format!("Could not update {}", path.display()))?;
+
+    if contents != original {
+        changes.push(Change::Update {
+            path: path.to_path_buf(),
+            contents,
+        });
+    }
+
+    Ok(())
+}
+
+/// Ensure the workspace members cover `dir`, either explicitly or via the
+/// `day-*` glob.
+fn register_member(manifest: &str, dir: &str) -> Result<String> {
+    let explicit = format!("\"{}\"", dir);
+    let globbed = !dir.contains('/') && manifest.contains("\"day-*\"");
+    if globbed || manifest.contains(&explicit) {
+        return Ok(manifest.to_string());
+    }
+
+    let start = manifest
+        .find("members = [")
+        .ok_or_else(|| anyhow::anyhow!("no workspace members found"))?;
+    let end = manifest[start..]
+        .find(']')
+        .map(|idx| idx + start)
+        .ok_or_else(|| anyhow::anyhow!("unterminated workspace members"))?;
+
+    let mut out = String::with_capacity(manifest.len() + explicit.len() + 6);
+    out.push_str(&manifest[..end]);
+    out.push_str("    ");
+    out.push_str(&explicit);
+    out.push_str(",\n");
+    out.push_str(&manifest[end..]);
+    Ok(out)
+}
+
+fn append_dependency(manifest: &str, project: &str, dep: &str) -> Result<String> {
+    let key = format!("{} =", project);
+    if manifest.lines().any(|l| l.trim_start().starts_with(&key)) {
+        return Ok(manifest.to_string());
+    }
+
+    let mut out = manifest.to_string();
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str(dep);
+    out.push('\n');
+    Ok(out)
+}
+
+/// Insert `entry` on the line(s) before `marker`, matching the marker's
+/// indentation, unless the entry is already present.
+fn insert_before_marker(contents: &str, marker: &str, entry: &str) -> Result<String> {
+    let marker_pos = contents
+        .find(marker)
+        .ok_or_else(|| anyhow::anyhow!("missing '{}'", marker))?;
+    let line_start = contents[..marker_pos].rfind('\n').map_or(0, |idx| idx + 1);
+    let indent = &contents[line_start..marker_pos];
+
+    let indented: String = entry
+        .lines()
+        .map(|l| format!("{}{}\n", indent, l))
+        .collect();
+
+    if contents.contains(indented.trim()) {
+        return Ok(contents.to_string());
+    }
+
+    let mut out = String::with_capacity(contents.len() + indented.len());
+    out.push_str(&contents[..line_start]);
+    out.push_str(&indented);
+    out.push_str(&contents[line_start..]);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names() {
+        let names = Names::new(2025, 3, "foo-bar-baz").unwrap();
+        assert_eq!(names.crate_ident, "foo_bar_baz");
+        assert_eq!(names.struct_name, "FooBarBaz");
+        assert_eq!(names.title, "foo bar baz");
+        assert_eq!(names.title_case, "Foo Bar Baz");
+        assert_eq!(names.dir, "day-003-foo-bar-baz");
+        assert_eq!(names.rel_dir, "day-003-foo-bar-baz");
+        assert_eq!(names.workspace_path(), "..");
+        assert_eq!(names.bench_ident(), "day_003");
+
+        let names = Names::new(2024, 3, "foo-bar-baz").unwrap();
+        assert_eq!(names.rel_dir, "2024/day-003-foo-bar-baz");
+        assert_eq!(names.workspace_path(), "../..");
+        assert_eq!(names.bench_ident(), "day_2024_003");
+
+        assert!(Names::new(2025, 3, "Foo").is_err());
+        assert!(Names::new(2025, 3, "foo--bar").is_err());
+        assert!(Names::new(2025, 3, "1foo").is_err());
+        assert!(Names::new(2025, 0, "foo").is_err());
+    }
+
+    #[test]
+    fn marker_insertion_is_idempotent() {
+        let original = "generate_cli! {\n    (Foo, 1),\n    // command_marker\n}\n";
+        let once = insert_before_marker(original, COMMAND_MARKER, "(Bar, 2),").unwrap();
+        assert_eq!(
+            once,
+            "generate_cli! {\n    (Foo, 1),\n    (Bar, 2),\n    // command_marker\n}\n"
+        );
+        let twice = insert_before_marker(&once, COMMAND_MARKER, "(Bar, 2),").unwrap();
+        assert_eq!(once, twice);
+    }
+
+    #[test]
+    fn dependency_and_member_registration() {
+        let manifest = "[dependencies]\nfoo = { path = \"../day-001-foo\" }\n";
+        let dep = "bar = { path = \"../day-002-bar\" }";
+        let once = append_dependency(manifest, "bar", dep).unwrap();
+        assert!(once.ends_with("bar = { path = \"../day-002-bar\" }\n"));
+        assert_eq!(once, append_dependency(&once, "bar", dep).unwrap());
+
+        let workspace = "[workspace]\nmembers = [\n    \"aoc-cli\",\n]\n";
+        let once = register_member(workspace, "day-002-bar").unwrap();
+        assert_eq!(
+            once,
+            "[workspace]\nmembers = [\n    \"aoc-cli\",\n    \"day-002-bar\",\n]\n"
+        );
+        assert_eq!(once, register_member(&once, "day-002-bar").unwrap());
+
+        let globbed = "[workspace]\nmembers = [\n    \"day-*\",\n]\n";
+        assert_eq!(globbed, register_member(globbed, "day-002-bar").unwrap());
+        assert_ne!(
+            globbed,
+            register_member(globbed, "2024/day-002-bar").unwrap()
+        );
+    }
+
+    /// Every file under `dir`, with its contents.
+    fn snapshot(dir: &Path) -> Vec<(PathBuf, String)> {
+        let mut files = Vec::default();
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                files.extend(snapshot(&path));
+            } else {
+                files.push((path.clone(), fs::read_to_string(&path).unwrap()));
+            }
+        }
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn scaffolding() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for (rel, contents) in [
+            (
+                "Cargo.toml",
+                "[workspace]\nmembers = [\n    \"day-*\",\n]\n",
+            ),
+            (
+                "aoc2025/Cargo.toml",
+                "[features]\nall = [\n    # all_marker\n]\n# feature_marker\n\n[dependencies]\n",
+            ),
+            (
+                "aoc2025/src/lib.rs",
+                "macro_rules! registry {\n    () => {\n        // facade_marker\n    };\n}\n",
+            ),
+            ("aoc-benchmarking/Cargo.toml", "[dependencies]\n"),
+            (
+                "aoc-benchmarking/benches/bench_main.rs",
+                "// import_marker\naoc_benches! {\n    // bench_marker\n}\n",
+            ),
+        ] {
+            fs::create_dir_all(root.join(rel).parent().unwrap()).unwrap();
+            fs::write(root.join(rel), contents).unwrap();
+        }
+
+        let (config, _) = Config::load_from(None, None, |_| None, false);
+        let new = |day, name: &str, dry_run| New {
+            day,
+            name: name.to_string(),
+            year: None,
+            root: Some(root.to_path_buf()),
+            dry_run,
+        };
+
+        // a dry run only reports what it would do
+        let before = snapshot(root);
+        new(13, "foo-bar", true).run(&config).unwrap();
+        assert_eq!(snapshot(root), before);
+
+        new(13, "foo-bar", false).run(&config).unwrap();
+        let after = snapshot(root);
+        assert!(root.join("day-013-foo-bar/src/lib.rs").is_file());
+        let registry = fs::read_to_string(root.join("aoc2025/src/lib.rs")).unwrap();
+        assert!(registry.contains("(FooBar, foo_bar, \"day-013\", 2025, 13),\n"));
+
+        // running it again registers nothing twice
+        let names = Names::new(DEFAULT_YEAR, 13, "foo-bar").unwrap();
+        assert!(plan(root, &names).unwrap().is_empty());
+        new(13, "foo-bar", false).run(&config).unwrap();
+        assert_eq!(snapshot(root), after);
+        for (rel, entry) in [
+            ("aoc2025/Cargo.toml", "\"day-013\","),
+            ("aoc2025/Cargo.toml", "day-013 = [\"dep:foo-bar\"]"),
+            ("aoc2025/Cargo.toml", "foo-bar = {"),
+            ("aoc2025/src/lib.rs", "(FooBar, foo_bar,"),
+            ("aoc-benchmarking/Cargo.toml", "foo-bar = {"),
+            (
+                "aoc-benchmarking/benches/bench_main.rs",
+                "use foo_bar::FooBar;",
+            ),
+            ("aoc-benchmarking/benches/bench_main.rs", "day_013,"),
+        ] {
+            let contents = fs::read_to_string(root.join(rel)).unwrap();
+            assert_eq!(contents.matches(entry).count(), 1, "{}:\n{}", rel, contents);
+        }
+
+        // a different crate for the same day is never overwritten
+        let err = new(13, "baz", false).run(&config).unwrap_err();
+        assert!(err.to_string().contains("refusing to overwrite"), "{}", err);
+        assert!(!root.join("day-013-baz").exists());
+        assert_eq!(snapshot(root), after);
+    }
+}
@@ -0,0 +1,537 @@
+use std::{
+    io::Read,
+    num::NonZeroUsize,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, RecvTimeoutError},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Result, anyhow};
+use aoc_plumbing::DynAnswer;
+use clap::Args;
+use serde::Serialize;
+use serde_json::json;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::{
+    cli::{problems, solve_dyn},
+    config::Config,
+};
+
+/// Serve the solutions over HTTP.
+///
+/// `GET /days` lists the implemented days for the year, and
+/// `POST /days/{day}/solve` solves a day using the request body as the input.
+/// Solutions are returned as the same JSON as `run --json`, with an added
+/// `timing` object. Failures are returned as
+/// `{"error": {"kind": ..., "message": ...}}` with an appropriate status,
+/// including a 503 when `--max-concurrent` requests are already in flight.
+#[derive(Args)]
+pub(crate) struct Serve {
+    /// The address to listen on.
+    #[clap(short, long, default_value = "127.0.0.1:8025")]
+    addr: String,
+
+    /// The year to serve.
+    ///
+    /// Defaults to the `year` from the config.
+    #[clap(short, long)]
+    year: Option<usize>,
+
+    /// The largest accepted input, in bytes.
+    #[clap(long, default_value_t = 1 << 20)]
+    max_body: usize,
+
+    /// How long to wait for a solution before giving up, in seconds.
+    ///
+    /// The solver itself cannot be interrupted, so it will keep running in the
+    /// background, but the client gets its answer.
+    #[clap(long, default_value_t = 10)]
+    timeout: u64,
+
+    /// The most requests to handle at once, beyond which requests are turned
+    /// away with a 503.
+    ///
+    /// A solver that outlives its timeout still counts against this until it
+    /// finishes. Defaults to the number of cpus.
+    #[clap(long)]
+    max_concurrent: Option<NonZeroUsize>,
+}
+
+impl Serve {
+    pub fn run(&self, config: &Config) -> Result<()> {
+        let limits = Limits {
+            year: config.year(self.year),
+            max_body: self.max_body,
+            timeout: Duration::from_secs(self.timeout),
+        };
+
+        let max_concurrent = self
+            .max_concurrent
+            .or_else(|| thread::available_parallelism().ok())
+            .map_or(1, NonZeroUsize::get);
+
+        let server = Server::http(&self.addr)
+            .map_err(|e| anyhow!("Could not listen on {}: {}", self.addr, e))?;
+        println!("listening on http://{}", server.server_addr());
+
+        serve(&server, limits, &Slots::new(max_concurrent));
+
+        Ok(())
+    }
+}
+
+/// Handle each request on its own thread, as long as there's a free slot.
+fn serve(server: &Server, limits: Limits, slots: &Slots) {
+    for request in server.incoming_requests() {
+        match slots.try_acquire() {
+            Some(slot) => {
+                thread::spawn(move || handle(request, &limits, Arc::new(slot)));
+            }
+            None => respond(request, Reply::busy()),
+        }
+    }
+}
+
+/// A fixed number of slots for requests in flight.
+#[derive(Debug, Clone)]
+struct Slots {
+    in_use: Arc<AtomicUsize>,
+    max: usize,
+}
+
+impl Slots {
+    fn new(max: usize) -> Self {
+        Self {
+            in_use: Arc::default(),
+            max,
+        }
+    }
+
+    /// Claim a slot, if any are free.
+    fn try_acquire(&self) -> Option<Slot> {
+        self.in_use
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (n < self.max).then_some(n + 1)
+            })
+            .ok()?;
+
+        Some(Slot(self.in_use.clone()))
+    }
+}
+
+/// A claimed slot, freed when dropped.
+///
+/// The request and its solver share one, so a solver that's still running
+/// after the request timed out keeps it claimed.
+#[derive(Debug)]
+struct Slot(Arc<AtomicUsize>);
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Limits {
+    year: usize,
+    max_body: usize,
+    timeout: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Reply {
+    status: u16,
+    /// The body as json text, since a `Value` can't hold every answer.
+    body: String,
+}
+
+impl Reply {
+    fn ok(body: &impl Serialize) -> Self {
+        match serde_json::to_string(body) {
+            Ok(body) => Self { status: 200, body },
+            Err(e) => Self::error(500, "internal", e),
+        }
+    }
+
+    fn error(status: u16, kind: &str, message: impl ToString) -> Self {
+        Self {
+            status,
+            body: json!({ "error": { "kind": kind, "message": message.to_string() } })
+                .to_string(),
+        }
+    }
+
+    fn busy() -> Self {
+        Self::error(503, "busy", "too many requests in flight, try again later")
+    }
+
+    fn too_large(limit: usize) -> Self {
+        Self::error(
+            413,
+            "too_large",
+            format!("input exceeds the limit of {} bytes", limit),
+        )
+    }
+}
+
+fn handle(mut request: Request, limits: &Limits, slot: Arc<Slot>) {
+    let reply = if request
+        .body_length()
+        .is_some_and(|len| len > limits.max_body)
+    {
+        // no point reading it if we already know it's too big
+        Reply::too_large(limits.max_body)
+    } else {
+        let method = request.method().clone();
+        let url = request.url().to_string();
+        route(&method, &url, request.as_reader(), limits, slot.clone())
+    };
+
+    respond(request, reply);
+    drop(slot);
+}
+
+fn respond(request: Request, reply: Reply) {
+    let response = Response::from_string(reply.body)
+        .with_status_code(reply.status)
+        .with_header(
+            Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+                .expect("static header is valid"),
+        );
+
+    if let Err(e) = request.respond(response) {
+        eprintln!("failed to respond: {}", e);
+    }
+}
+
+fn route(
+    method: &Method,
+    url: &str,
+    body: &mut dyn Read,
+    limits: &Limits,
+    slot: Arc<Slot>,
+) -> Reply {
+    let path = url.split('?').next().unwrap_or_default();
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    match (method, segments.as_slice()) {
+        (Method::Get, ["days"]) => list(limits.year),
+        (Method::Post, ["days", day, "solve"]) => {
+            let Ok(day) = day.parse::<usize>() else {
+                return Reply::error(400, "bad_request", format!("invalid day '{}'", day));
+            };
+
+            if !problems()
+                .iter()
+                .any(|p| p.year == limits.year && p.day == day)
+            {
+                return Reply::error(
+                    404,
+                    "not_implemented",
+                    format!("{} day {} is not implemented", limits.year, day),
+                );
+            }
+
+            match read_body(body, limits.max_body) {
+                Ok(input) => solve(limits.year, day, input, limits.timeout, slot),
+                Err(reply) => reply,
+            }
+        }
+        (_, ["days"]) | (_, ["days", _, "solve"]) => Reply::error(
+            405,
+            "method_not_allowed",
+            format!("{} is not supported for {}", method, path),
+        ),
+        _ => Reply::error(404, "not_found", format!("no route for {}", path)),
+    }
+}
+
+fn list(year: usize) -> Reply {
+    let days: Vec<_> = problems().into_iter().filter(|p| p.year == year).collect();
+    Reply::ok(&days)
+}
+
+/// Read at most `limit` bytes, as chunked requests have no length up front.
+fn read_body(body: &mut dyn Read, limit: usize) -> Result<String, Reply> {
+    let mut input = String::new();
+    body.take(limit as u64 + 1)
+        .read_to_string(&mut input)
+        .map_err(|e| Reply::error(400, "bad_request", format!("unreadable body: {}", e)))?;
+
+    if input.len() > limit {
+        return Err(Reply::too_large(limit));
+    }
+
+    Ok(input)
+}
+
+fn solve(year: usize, day: usize, input: String, timeout: Duration, slot: Arc<Slot>) -> Reply {
+    let result = with_timeout(timeout, move || {
+        // held until the solver finishes, even if nobody's waiting for it
+        let _slot = slot;
+        let start = Instant::now();
+        let solution = solve_dyn(year, day, &input);
+        (solution, start.elapsed())
+    });
+
+    match result {
+        Ok((None, _)) => Reply::error(
+            404,
+            "not_implemented",
+            format!("{} day {} is not implemented", year, day),
+        ),
+        Ok((Some(Ok(solution)), elapsed)) => Reply::ok(&Solved {
+            part_one: &solution.part_one,
+            part_two: &solution.part_two,
+            timing: Timing {
+                elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+            },
+        }),
+        Ok((Some(Err(e)), _)) => Reply::error(422, "solve_failed", format!("{:#}", e)),
+        Err(RecvTimeoutError::Timeout) => Reply::error(
+            504,
+            "timeout",
+            format!("no solution within {}s", timeout.as_secs_f64()),
+        ),
+        Err(RecvTimeoutError::Disconnected) => Reply::error(500, "panicked", "the solver panicked"),
+    }
+}
+
+/// The same as `run --json`, plus timing.
+#[derive(Serialize)]
+struct Solved<'a> {
+    part_one: &'a DynAnswer,
+    part_two: &'a DynAnswer,
+    timing: Timing,
+}
+
+#[derive(Serialize)]
+struct Timing {
+    elapsed_ms: f64,
+}
+
+/// Run `f` on its own thread, waiting at most `timeout` for it to finish.
+fn with_timeout<T, F>(timeout: Duration, f: F) -> Result<T, RecvTimeoutError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        // the receiver is gone if we timed out, which is fine
+        let _ = tx.send(f());
+    });
+
+    rx.recv_timeout(timeout)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+
+    const EXAMPLE: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+
+    fn limits() -> Limits {
+        Limits {
+            year: 2025,
+            max_body: 64,
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    fn slot() -> Arc<Slot> {
+        Arc::new(Slots::new(1).try_acquire().unwrap())
+    }
+
+    fn body(reply: &Reply) -> Value {
+        serde_json::from_str(&reply.body).unwrap()
+    }
+
+    #[test]
+    fn routes() {
+        let reply = route(&Method::Get, "/days", &mut "".as_bytes(), &limits(), slot());
+        assert_eq!(reply.status, 200);
+        assert_eq!(body(&reply)[0]["day"], 1);
+        assert_eq!(body(&reply)[0]["label"], "001 secret entrance");
+
+        let reply = route(
+            &Method::Post,
+            "/days/1/solve",
+            &mut EXAMPLE.as_bytes(),
+            &limits(),
+            slot(),
+        );
+        assert_eq!(reply.status, 200);
+        let mut solved = body(&reply);
+        assert!(solved["timing"]["elapsed_ms"].is_number());
+        solved.as_object_mut().unwrap().remove("timing");
+        assert_eq!(solved, json!({ "part_one": 3, "part_two": 6 }));
+
+        // more fresh ids than fit in a u64
+        let reply = route(
+            &Method::Post,
+            "/days/5/solve",
+            &mut format!("0-{}\n\n0", u64::MAX).as_bytes(),
+            &limits(),
+            slot(),
+        );
+        assert_eq!(reply.status, 200);
+        assert!(
+            reply
+                .body
+                .starts_with(r#"{"part_one":1,"part_two":18446744073709551616,"#)
+        );
+
+        let reply = route(
+            &Method::Post,
+            "/days/1/solve",
+            &mut "L".as_bytes(),
+            &limits(),
+            slot(),
+        );
+        assert_eq!(reply.status, 422);
+        assert_eq!(body(&reply)["error"]["kind"], "solve_failed");
+
+        let big = "L1\n".repeat(100);
+        let reply = route(
+            &Method::Post,
+            "/days/1/solve",
+            &mut big.as_bytes(),
+            &limits(),
+            slot(),
+        );
+        assert_eq!(reply.status, 413);
+
+        let reply = route(
+            &Method::Post,
+            "/days/26/solve",
+            &mut "".as_bytes(),
+            &limits(),
+            slot(),
+        );
+        assert_eq!(reply.status, 404);
+        assert_eq!(body(&reply)["error"]["kind"], "not_implemented");
+
+        let reply = route(
+            &Method::Post,
+            "/days/foo/solve",
+            &mut "".as_bytes(),
+            &limits(),
+            slot(),
+        );
+        assert_eq!(reply.status, 400);
+
+        let reply = route(
+            &Method::Get,
+            "/days/1/solve",
+            &mut "".as_bytes(),
+            &limits(),
+            slot(),
+        );
+        assert_eq!(reply.status, 405);
+
+        let reply = route(&Method::Get, "/nope", &mut "".as_bytes(), &limits(), slot());
+        assert_eq!(reply.status, 404);
+    }
+
+    #[test]
+    fn timeouts() {
+        let result = with_timeout(Duration::from_millis(10), || {
+            thread::sleep(Duration::from_secs(1));
+        });
+        assert_eq!(result, Err(RecvTimeoutError::Timeout));
+
+        assert_eq!(with_timeout(Duration::from_secs(1), || 5), Ok(5));
+    }
+
+    #[test]
+    fn over_http() {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let addr = server.server_addr().to_ip().unwrap();
+        let slots = Slots::new(1);
+        let serving = slots.clone();
+        thread::spawn(move || serve(&server, limits(), &serving));
+
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .build()
+            .into();
+
+        let mut res = agent
+            .post(format!("http://{}/days/1/solve", addr))
+            .send(EXAMPLE)
+            .unwrap();
+        assert_eq!(res.status(), 200);
+        let body: Value = serde_json::from_str(&res.body_mut().read_to_string().unwrap()).unwrap();
+        assert_eq!(body["part_two"], 6);
+
+        let res = agent
+            .post(format!("http://{}/days/1/solve", addr))
+            .send("L1\n".repeat(100))
+            .unwrap();
+        assert_eq!(res.status(), 413);
+
+        // with the only slot taken, requests are turned away (once the last
+        // handler has let go of it)
+        let slot = loop {
+            if let Some(slot) = slots.try_acquire() {
+                break slot;
+            }
+            thread::yield_now();
+        };
+        let mut res = agent
+            .post(format!("http://{}/days/1/solve", addr))
+            .send(EXAMPLE)
+            .unwrap();
+        assert_eq!(res.status(), 503);
+        let body: Value = serde_json::from_str(&res.body_mut().read_to_string().unwrap()).unwrap();
+        assert_eq!(body["error"]["kind"], "busy");
+
+        drop(slot);
+        let res = agent
+            .post(format!("http://{}/days/1/solve", addr))
+            .send(EXAMPLE)
+            .unwrap();
+        assert_eq!(res.status(), 200);
+    }
+
+    #[test]
+    fn slots() {
+        let slots = Slots::new(2);
+        let a = slots.try_acquire().unwrap();
+        let b = Arc::new(slots.try_acquire().unwrap());
+        assert!(slots.try_acquire().is_none());
+
+        drop(a);
+        let a = slots.try_acquire().unwrap();
+        assert!(slots.try_acquire().is_none());
+
+        // a solver that timed out keeps its slot until it's done
+        let (tx, rx) = mpsc::channel::<()>();
+        let solver = b.clone();
+        let result = with_timeout(Duration::from_millis(10), move || {
+            let _slot = solver;
+            let _ = rx.recv();
+        });
+        assert_eq!(result, Err(RecvTimeoutError::Timeout));
+        drop(b);
+        assert!(slots.try_acquire().is_none());
+
+        tx.send(()).unwrap();
+        let start = Instant::now();
+        while slots.in_use.load(Ordering::Acquire) > 1 {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            thread::yield_now();
+        }
+        assert!(slots.try_acquire().is_some());
+        drop(a);
+    }
+}
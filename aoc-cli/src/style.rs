@@ -0,0 +1,37 @@
+use std::fmt::Display;
+
+/// Minimal ANSI styling that can be switched off wholesale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Painter {
+    enabled: bool,
+}
+
+impl Painter {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    fn paint(&self, code: &str, val: impl Display) -> String {
+        if self.enabled {
+            format!("\x1b[{}m{}\x1b[0m", code, val)
+        } else {
+            val.to_string()
+        }
+    }
+
+    pub fn bold(&self, val: impl Display) -> String {
+        self.paint("1", val)
+    }
+
+    pub fn dim(&self, val: impl Display) -> String {
+        self.paint("2", val)
+    }
+
+    pub fn red(&self, val: impl Display) -> String {
+        self.paint("31", val)
+    }
+
+    pub fn yellow(&self, val: impl Display) -> String {
+        self.paint("33", val)
+    }
+}
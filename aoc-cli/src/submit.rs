@@ -0,0 +1,178 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+
+use crate::{
+    cli::solve_dyn,
+    client::{AocClient, Verdict},
+    config::Config,
+    history::{History, now},
+    workspace::{find_workspace_root, input_path},
+};
+
+/// Solve and submit the answer for one part of a given day.
+///
+/// Every attempt and the server's verdict is recorded in `.aoc/history.json`
+/// in the workspace root, or in the input root when not run from inside a
+/// workspace. Answers that were already rejected, or that fall
+/// outside of previously reported too-high/too-low bounds, are not submitted,
+/// nor is anything submitted while the server has asked us to wait.
+#[derive(Args)]
+pub(crate) struct Submit {
+    /// The day to submit.
+    day: usize,
+
+    /// The year of the day to submit.
+    ///
+    /// Defaults to the `year` from the config.
+    #[clap(short, long)]
+    year: Option<usize>,
+
+    /// The part to submit.
+    #[clap(value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+
+    /// The input to solve.
+    ///
+    /// Defaults to the conventional `day-NNN-*/input.txt`.
+    #[clap(short, long)]
+    input: Option<PathBuf>,
+
+    /// The session token (the value of the `session` cookie).
+    ///
+    /// This may be specified instead by setting `session` in the config, or
+    /// by setting the `AOC_SESSION` env var.
+    #[clap(long)]
+    session: Option<String>,
+
+    /// The base url of the Advent of Code site.
+    ///
+    /// This is mainly useful for pointing at a local stub server.
+    ///
+    /// Defaults to the `base_url` from the config.
+    #[clap(long)]
+    base_url: Option<String>,
+
+    /// The directory containing the `day-NNN-*` crates.
+    ///
+    /// Defaults to the `input_root` from the config.
+    #[clap(long)]
+    root: Option<PathBuf>,
+}
+
+impl Submit {
+    pub fn run(&self, config: &Config) -> Result<()> {
+        let root = config.input_root(self.root.as_ref());
+        let year = config.year(self.year);
+
+        let input_file = match self.input {
+            Some(ref input) => input.clone(),
+            None => input_path(&root, year, self.day)?,
+        };
+
+        let answer = self.answer(year, &input_file)?;
+
+        let session = config.session(self.session.as_ref())?;
+        let client = AocClient::new(config.base_url(self.base_url.as_ref()), &session);
+        // the history is kept in one place however the inputs are laid out
+        let history_root = find_workspace_root().unwrap_or(root);
+        let verdict = submit(&client, &history_root, year, self.day, self.part, &answer)?;
+        println!(
+            "{} day {} part {}: {} was {}",
+            year, self.day, self.part, answer, verdict
+        );
+
+        if let Verdict::Cooldown { wait } = verdict {
+            println!("try again in {}s", wait);
+        }
+
+        Ok(())
+    }
+
+    fn answer(&self, year: usize, input_file: &Path) -> Result<String> {
+        let input = std::fs::read_to_string(input_file).context("Could not read input file")?;
+        let solution = solve_dyn(year, self.day, &input)
+            .ok_or_else(|| anyhow::anyhow!("{} day {} is not implemented", year, self.day))??;
+
+        Ok(match self.part {
+            1 => solution.part_one.to_string(),
+            _ => solution.part_two.to_string(),
+        })
+    }
+}
+
+/// Submit `answer` unless the history in `history_root` says it's pointless,
+/// recording the outcome.
+fn submit(
+    client: &AocClient,
+    history_root: &Path,
+    year: usize,
+    day: usize,
+    part: u8,
+    answer: &str,
+) -> Result<Verdict> {
+    let mut history = History::load(history_root)?;
+    let part_history = history.part_mut(year, day, part);
+
+    if let Err(refusal) = part_history.check(answer, now()) {
+        bail!("Not submitting {}: {}", answer, refusal);
+    }
+
+    let verdict = client.answer(year, day, part, answer)?;
+    part_history.record(answer, verdict, now());
+    history.save()?;
+
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::test_server;
+
+    use super::*;
+
+    #[test]
+    fn records_and_refuses() {
+        let root = tempfile::tempdir().unwrap();
+        let (base_url, requests) = test_server::serve(vec![
+            (
+                200,
+                "<article><p>That's not the right answer; your answer is too high. \
+                 Please wait one minute before trying again.</p></article>"
+                    .into(),
+            ),
+            (
+                200,
+                "<article><p>That's the right answer!</p></article>".into(),
+            ),
+        ]);
+        let client = AocClient::new(&base_url, "abc");
+
+        let verdict = submit(&client, root.path(), 2025, 3, 1, "500").unwrap();
+        assert_eq!(verdict, Verdict::TooHigh { wait: 60 });
+
+        let req = requests.recv().unwrap();
+        assert_eq!(req.request_line, "POST /2025/day/3/answer HTTP/1.1");
+        assert_eq!(req.body, "level=1&answer=500");
+
+        // known too high, so neither of these should reach the server
+        assert!(submit(&client, root.path(), 2025, 3, 1, "500").is_err());
+        assert!(submit(&client, root.path(), 2025, 3, 1, "600").is_err());
+        // and we're still cooling down
+        assert!(submit(&client, root.path(), 2025, 3, 1, "400").is_err());
+        assert!(requests.try_recv().is_err());
+
+        // pretend the wait is over
+        let mut history = History::load(root.path()).unwrap();
+        history.part_mut(2025, 3, 1).wait_until = None;
+        history.save().unwrap();
+
+        let verdict = submit(&client, root.path(), 2025, 3, 1, "400").unwrap();
+        assert_eq!(verdict, Verdict::Correct);
+
+        let mut history = History::load(root.path()).unwrap();
+        assert_eq!(history.part_mut(2025, 3, 1).attempts.len(), 2);
+        assert!(submit(&client, root.path(), 2025, 3, 1, "400").is_err());
+    }
+}
@@ -0,0 +1,104 @@
+use std::{io::IsTerminal, path::PathBuf};
+
+use anyhow::{Context, Result, bail};
+use aoc_plumbing::viz::{Scene, ansi, svg};
+use clap::{Args, ValueEnum};
+
+use crate::config::Config;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum Format {
+    Svg,
+    Ansi,
+}
+
+/// Draw a visualization of a day's input.
+///
+/// Only some days can be visualized: those of 2025 whose problem implements
+/// `Visualize`, which are marked as such in the `aoc2025` registry.
+#[derive(Args)]
+pub(crate) struct Viz {
+    /// The day to visualize.
+    day: usize,
+
+    /// The path to the input to visualize.
+    input: PathBuf,
+
+    /// The year of the day to visualize.
+    ///
+    /// Defaults to the `year` from the config.
+    #[clap(short, long)]
+    year: Option<usize>,
+
+    /// The output format.
+    #[clap(short, long, value_enum, default_value_t = Format::Ansi)]
+    format: Format,
+
+    /// The widest the ansi output may be, in characters.
+    #[clap(short, long, default_value_t = 160)]
+    width: usize,
+
+    /// Write the output to this file instead of stdout.
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+}
+
+impl Viz {
+    pub fn run(&self, config: &Config) -> Result<()> {
+        let year = config.year(self.year);
+        let input = std::fs::read_to_string(&self.input).context("Could not read input file")?;
+
+        let Some(scene) = scene(year, self.day, &input) else {
+            bail!("{} day {} has no visualization", year, self.day);
+        };
+        let scene = scene?;
+
+        let rendered = match self.format {
+            Format::Svg => svg::render(&scene),
+            Format::Ansi => {
+                // colors only make sense when they'll end up in a terminal
+                let color =
+                    config.colors.value && self.output.is_none() && std::io::stdout().is_terminal();
+                ansi::render(&scene, self.width, color)
+            }
+        };
+
+        match &self.output {
+            Some(path) => std::fs::write(path, rendered)
+                .with_context(|| format!("Could not write {}", path.display())),
+            None => {
+                print!("{}", rendered);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Build the scene for the given day, if that day can be visualized.
+fn scene(year: usize, day: usize, input: &str) -> Option<Result<Scene>> {
+    if year != aoc2025::YEAR {
+        return None;
+    }
+
+    match aoc2025::visualize(day, input) {
+        Err(aoc2025::Error::NoVisualization(_) | aoc2025::Error::NotAvailable(_)) => None,
+        result => Some(result.map_err(Into::into)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_some_days() {
+        assert!(scene(2025, 1, "").is_none());
+        assert!(scene(2024, 4, "").is_none());
+
+        let scene = scene(2025, 9, "7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3")
+            .unwrap()
+            .unwrap();
+        assert_eq!(scene.width, 9.0);
+        assert_eq!(scene.height, 6.0);
+    }
+}
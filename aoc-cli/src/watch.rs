@@ -0,0 +1,304 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use aoc_plumbing::{DynAnswer, DynSolution};
+use clap::Args;
+
+use crate::{
+    cli::{print_timing, problems, solve_dyn},
+    config::Config,
+    style::Painter,
+};
+
+/// Re-solve a day whenever any of the given inputs change.
+///
+/// Each change prints the new answers alongside how they differ from the
+/// previous run for that file. Parse and solve errors are printed, but don't
+/// stop the watch. Stop with ctrl-c.
+#[derive(Args)]
+pub(crate) struct Watch {
+    /// The day to solve.
+    day: usize,
+
+    /// The inputs to watch, e.g. `input.txt example.txt`.
+    #[clap(required = true)]
+    paths: Vec<PathBuf>,
+
+    /// The year of the day to solve.
+    ///
+    /// Defaults to the `year` from the config.
+    #[clap(short, long)]
+    year: Option<usize>,
+
+    /// How often to check for changes, in milliseconds.
+    #[clap(long, default_value_t = 250)]
+    interval: u64,
+}
+
+impl Watch {
+    pub fn run(&self, config: &Config) -> Result<()> {
+        let year = config.year(self.year);
+        if !problems()
+            .iter()
+            .any(|p| p.year == year && p.day == self.day)
+        {
+            anyhow::bail!("{} day {} is not implemented", year, self.day);
+        }
+
+        let painter = Painter::new(config.colors.value);
+        let mut session = Session::new(year, self.day, self.paths.clone());
+
+        println!(
+            "{}",
+            painter.dim(format!(
+                "watching {} file(s) for {} day {}",
+                self.paths.len(),
+                year,
+                self.day
+            ))
+        );
+
+        loop {
+            for report in session.poll() {
+                report.print(&painter);
+            }
+            thread::sleep(Duration::from_millis(self.interval));
+        }
+    }
+}
+
+/// How an answer compares to the previous run.
+#[derive(Debug, Clone, PartialEq)]
+enum Diff {
+    First,
+    Unchanged,
+    Changed(DynAnswer),
+}
+
+impl Diff {
+    fn new(previous: Option<&DynAnswer>, current: &DynAnswer) -> Self {
+        match previous {
+            None => Self::First,
+            Some(prev) if prev == current => Self::Unchanged,
+            Some(prev) => Self::Changed(prev.clone()),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Outcome {
+    Solved {
+        solution: DynSolution,
+        diffs: (Diff, Diff),
+        elapsed: Duration,
+    },
+    Failed(String),
+}
+
+#[derive(Debug)]
+struct Report {
+    path: PathBuf,
+    outcome: Outcome,
+}
+
+impl Report {
+    fn print(&self, painter: &Painter) {
+        println!("{}", painter.bold(self.path.display()));
+        match &self.outcome {
+            Outcome::Solved {
+                solution,
+                diffs,
+                elapsed,
+            } => {
+                print_answer(1, &solution.part_one, &diffs.0, painter);
+                print_answer(2, &solution.part_two, &diffs.1, painter);
+                print_timing(*elapsed, painter);
+            }
+            Outcome::Failed(e) => println!("{} {}", painter.red("error:"), e),
+        }
+    }
+}
+
+fn print_answer(part: u8, answer: &DynAnswer, diff: &Diff, painter: &Painter) {
+    let note = match diff {
+        Diff::First => String::new(),
+        Diff::Unchanged => format!(" {}", painter.dim("(unchanged)")),
+        Diff::Changed(prev) => format!(" {}", painter.yellow(format!("(was {})", prev))),
+    };
+    println!(
+        "{} {}{}",
+        painter.dim(format!("part {}:", part)),
+        painter.bold(answer),
+        note
+    );
+}
+
+/// The state of a single watched file.
+#[derive(Debug, Default)]
+struct Watched {
+    /// What we last read, or why we couldn't read it.
+    seen: Option<Result<String, String>>,
+    /// The last successful solution, to diff against.
+    last: Option<DynSolution>,
+}
+
+/// Solves a year and day, as [`solve_dyn`] does.
+type SolveFn = fn(usize, usize, &str) -> Option<Result<DynSolution>>;
+
+struct Session {
+    year: usize,
+    day: usize,
+    paths: Vec<PathBuf>,
+    watched: Vec<Watched>,
+    solve: SolveFn,
+}
+
+impl Session {
+    fn new(year: usize, day: usize, paths: Vec<PathBuf>) -> Self {
+        Self::with_solver(year, day, paths, solve_dyn)
+    }
+
+    /// [`Session::new`], solving with `solve` so tests can swap it out.
+    fn with_solver(year: usize, day: usize, paths: Vec<PathBuf>, solve: SolveFn) -> Self {
+        let watched = paths.iter().map(|_| Watched::default()).collect();
+        Self {
+            year,
+            day,
+            paths,
+            watched,
+            solve,
+        }
+    }
+
+    /// Re-solve every file that changed since the last poll.
+    fn poll(&mut self) -> Vec<Report> {
+        let mut reports = Vec::default();
+
+        for (path, watched) in self.paths.iter().zip(self.watched.iter_mut()) {
+            let current = std::fs::read_to_string(path).map_err(|e| e.to_string());
+            if watched.seen.as_ref() == Some(&current) {
+                continue;
+            }
+
+            let outcome = match &current {
+                Ok(input) => {
+                    let start = Instant::now();
+                    // a panic should be reported like any other failure,
+                    // not end the watch
+                    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                        (self.solve)(self.year, self.day, input)
+                    }));
+                    let elapsed = start.elapsed();
+
+                    match result {
+                        Ok(Some(Ok(solution))) => {
+                            let prev = watched.last.as_ref();
+                            let diffs = (
+                                Diff::new(prev.map(|s| &s.part_one), &solution.part_one),
+                                Diff::new(prev.map(|s| &s.part_two), &solution.part_two),
+                            );
+                            watched.last = Some(solution.clone());
+                            Outcome::Solved {
+                                solution,
+                                diffs,
+                                elapsed,
+                            }
+                        }
+                        Ok(Some(Err(e))) => Outcome::Failed(format!("{:#}", e)),
+                        Ok(None) => Outcome::Failed(format!(
+                            "{} day {} is not implemented",
+                            self.year, self.day
+                        )),
+                        Err(_) => Outcome::Failed("the solver panicked".to_string()),
+                    }
+                }
+                Err(e) => Outcome::Failed(format!("could not read input: {}", e)),
+            };
+
+            watched.seen = Some(current);
+            reports.push(Report {
+                path: path.clone(),
+                outcome,
+            });
+        }
+
+        reports
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diffs(report: &Report) -> &(Diff, Diff) {
+        match &report.outcome {
+            Outcome::Solved { diffs, .. } => diffs,
+            Outcome::Failed(e) => panic!("unexpected failure: {}", e),
+        }
+    }
+
+    #[test]
+    fn reports_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let example = dir.path().join("example.txt");
+        let missing = dir.path().join("missing.txt");
+        std::fs::write(&example, "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82").unwrap();
+
+        let mut session = Session::new(2025, 1, vec![example.clone(), missing.clone()]);
+
+        let reports = session.poll();
+        assert_eq!(reports.len(), 2);
+        assert_eq!(diffs(&reports[0]), &(Diff::First, Diff::First));
+        assert!(matches!(reports[1].outcome, Outcome::Failed(_)));
+
+        // nothing changed, including the missing file still being missing
+        assert!(session.poll().is_empty());
+
+        // a parse error is reported, but doesn't lose the last answers
+        std::fs::write(&example, "L").unwrap();
+        let reports = session.poll();
+        assert_eq!(reports.len(), 1);
+        assert!(matches!(reports[0].outcome, Outcome::Failed(_)));
+
+        std::fs::write(&example, "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL182").unwrap();
+        let reports = session.poll();
+        assert_eq!(
+            diffs(&reports[0]),
+            &(Diff::Unchanged, Diff::Changed(DynAnswer::Number("6".into())))
+        );
+
+        std::fs::write(&missing, "R50").unwrap();
+        let reports = session.poll();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].path, missing);
+        assert_eq!(diffs(&reports[0]), &(Diff::First, Diff::First));
+    }
+
+    #[test]
+    fn survives_panics() {
+        let dir = tempfile::tempdir().unwrap();
+        let example = dir.path().join("example.txt");
+        std::fs::write(&example, "boom").unwrap();
+
+        let mut session =
+            Session::with_solver(2025, 1, vec![example.clone()], |year, day, input| {
+                assert_ne!(input, "boom", "boom");
+                solve_dyn(year, day, input)
+            });
+
+        let reports = session.poll();
+        assert!(matches!(
+            &reports[0].outcome,
+            Outcome::Failed(e) if e == "the solver panicked"
+        ));
+
+        std::fs::write(&example, "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82").unwrap();
+        let reports = session.poll();
+        assert_eq!(diffs(&reports[0]), &(Diff::First, Diff::First));
+    }
+}
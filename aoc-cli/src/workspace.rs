@@ -0,0 +1,72 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+use aoc_plumbing::DEFAULT_YEAR;
+
+/// Walk up from the current directory looking for the workspace manifest.
+pub(crate) fn find_workspace_root() -> Result<PathBuf> {
+    let cwd = std::env::current_dir().context("Could not determine current directory")?;
+    for dir in cwd.ancestors() {
+        let manifest = dir.join("Cargo.toml");
+        if manifest.is_file()
+            && fs::read_to_string(&manifest)
+                .map(|s| s.contains("[workspace]"))
+                .unwrap_or(false)
+        {
+            return Ok(dir.to_path_buf());
+        }
+    }
+
+    bail!("Could not find the workspace root from {}", cwd.display())
+}
+
+/// The directory holding the day crates for the given year.
+///
+/// Crates for the default year live directly in `root`, while those for any
+/// other year live in `root/<year>`.
+pub(crate) fn year_root(root: &Path, year: usize) -> PathBuf {
+    if year == DEFAULT_YEAR {
+        root.to_path_buf()
+    } else {
+        root.join(year.to_string())
+    }
+}
+
+/// Find the `day-NNN-*` crate directory for the given year and day under
+/// `root`.
+pub(crate) fn find_day_dir(root: &Path, year: usize, day: usize) -> Result<Option<PathBuf>> {
+    let root = year_root(root, year);
+    if !root.is_dir() {
+        return Ok(None);
+    }
+
+    let prefix = format!("day-{:03}-", day);
+    for entry in
+        fs::read_dir(&root).with_context(|| format!("Could not read {}", root.display()))?
+    {
+        let entry = entry?;
+        if entry.file_name().to_string_lossy().starts_with(&prefix) && entry.path().is_dir() {
+            return Ok(Some(entry.path()));
+        }
+    }
+
+    Ok(None)
+}
+
+/// The conventional input path for the given year and day,
+/// `day-NNN-*/input.txt`.
+pub(crate) fn input_path(root: &Path, year: usize, day: usize) -> Result<PathBuf> {
+    find_day_dir(root, year, day)?
+        .map(|dir| dir.join("input.txt"))
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "No crate for {} day {} in {}, try `aoc new` first",
+                year,
+                day,
+                year_root(root, year).display()
+            )
+        })
+}
@@ -0,0 +1,19 @@
+//! Tell the integration tests where cargo puts the cdylib.
+use std::{env, path::Path};
+
+fn main() {
+    // OUT_DIR is `<profile dir>/build/aoc-ffi-<hash>/out` whatever the target
+    // triple or target dir, and the cdylib that `cargo test` links against is
+    // built into `<profile dir>/deps` (it's only copied up a level by `cargo
+    // build`, so that copy may be stale)
+    let out_dir = env::var("OUT_DIR").expect("cargo sets OUT_DIR");
+    let profile_dir = Path::new(&out_dir)
+        .ancestors()
+        .nth(3)
+        .expect("OUT_DIR is nested in the profile dir");
+    println!(
+        "cargo:rustc-env=AOC_FFI_LIB_DIR={}",
+        profile_dir.join("deps").display()
+    );
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -0,0 +1,59 @@
+#ifndef AOC_FFI_H
+#define AOC_FFI_H
+
+/* Generated by cbindgen from aoc-ffi, do not edit. Regenerate with `just ffi-header`. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The outcome of a call to `aoc_solve`.
+ */
+typedef enum AocStatus {
+  AOC_STATUS_OK = 0,
+  AOC_STATUS_NOT_IMPLEMENTED = 1,
+  AOC_STATUS_INVALID_INPUT = 2,
+  AOC_STATUS_SOLVE_FAILED = 3,
+  AOC_STATUS_NULL_POINTER = 4,
+  /**
+   * Only returned when built with unwinding, as a panic otherwise aborts.
+   */
+  AOC_STATUS_PANICKED = 5,
+} AocStatus;
+
+/**
+ * Solve `day` of the default year using the `len` bytes at `input_ptr`.
+ *
+ * On return, `*out_json_buf` points to either the solution, as the same JSON
+ * printed by `aoc run --json`, or to `{"error": {"kind": ..., "message": ...}}`.
+ * Either way it must be released with `aoc_free_string`. If `out_json_buf` is
+ * null, `AOC_STATUS_NULL_POINTER` is returned and nothing is written.
+ *
+ * # Safety
+ *
+ * `input_ptr` must be valid for reads of `len` bytes (it may be null if `len`
+ * is zero), and `out_json_buf` must be null or valid for writing a pointer.
+ */
+enum AocStatus aoc_solve(uint32_t day, const uint8_t *input_ptr, size_t len, char **out_json_buf);
+
+/**
+ * The implemented days, as a JSON array of `{"year", "day", "label"}`.
+ *
+ * The result must be released with `aoc_free_string`.
+ */
+char *aoc_days(void);
+
+/**
+ * Release a string returned by this library. Passing null is a no-op.
+ *
+ * # Safety
+ *
+ * `s` must be null or a pointer previously returned by this library that has
+ * not already been freed.
+ */
+void aoc_free_string(char *s);
+
+#endif  /* AOC_FFI_H */
@@ -0,0 +1,154 @@
+//! A C ABI over the solutions registered in `aoc2025`, for calling them from
+//! other languages.
+//!
+//! Strings handed to the caller are NUL-terminated JSON owned by this library,
+//! and must be released with `aoc_free_string`. The header for this API lives
+//! in `include/aoc.h` and is generated by cbindgen (`just ffi-header`).
+//!
+//! The boundary is the one place `unsafe` can't be avoided, so it is confined
+//! to the `extern "C"` functions in this file. The solutions stay free of it.
+//!
+//! A panicking solver is reported as `AOC_STATUS_PANICKED` rather than
+//! unwinding into the caller, but only when the library is built with
+//! unwinding (the default). Under `panic = "abort"`, which the `release-ci`
+//! profile sets, there is nothing to catch and a panic aborts the whole host
+//! process, so build the library with `--release` instead when embedding it.
+use std::{
+    ffi::{CString, c_char},
+    panic::{self, AssertUnwindSafe},
+};
+
+use aoc2025::{Error, YEAR};
+use serde_json::{Value, json};
+
+/// The outcome of a call to `aoc_solve`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AocStatus {
+    Ok = 0,
+    NotImplemented = 1,
+    InvalidInput = 2,
+    SolveFailed = 3,
+    NullPointer = 4,
+    /// Only returned when built with unwinding, as a panic otherwise aborts.
+    Panicked = 5,
+}
+
+impl AocStatus {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::NotImplemented => "not_implemented",
+            Self::InvalidInput => "invalid_input",
+            Self::SolveFailed => "solve_failed",
+            Self::NullPointer => "null_pointer",
+            Self::Panicked => "panicked",
+        }
+    }
+
+    fn error(self, message: impl ToString) -> (Self, String) {
+        let body = json!({ "error": { "kind": self.kind(), "message": message.to_string() } });
+        (self, body.to_string())
+    }
+}
+
+fn days() -> Value {
+    aoc2025::problems()
+        .into_iter()
+        .map(|p| json!({ "year": p.year, "day": p.day, "label": p.label }))
+        .collect()
+}
+
+/// Solve `day` of the default year, producing the status and the JSON to hand
+/// back to the caller.
+pub fn solve_json(day: usize, input: &[u8]) -> (AocStatus, String) {
+    let Ok(input) = std::str::from_utf8(input) else {
+        return AocStatus::InvalidInput.error("input is not valid utf-8");
+    };
+
+    // unwinding across the ABI boundary would abort the caller (this does
+    // nothing under `panic = "abort"`, see the crate docs)
+    let result = panic::catch_unwind(AssertUnwindSafe(|| aoc2025::solve(day, input)));
+
+    match result {
+        Ok(Ok(solution)) => match serde_json::to_string(&solution) {
+            Ok(json) => (AocStatus::Ok, json),
+            Err(e) => AocStatus::SolveFailed.error(e),
+        },
+        Ok(Err(Error::NotAvailable(_))) => {
+            AocStatus::NotImplemented.error(format!("{} day {} is not implemented", YEAR, day))
+        }
+        Ok(Err(e)) => AocStatus::SolveFailed.error(format!("{:#}", anyhow::Error::from(e))),
+        Err(_) => AocStatus::Panicked.error("the solver panicked"),
+    }
+}
+
+fn into_raw(json: String) -> *mut c_char {
+    CString::new(json)
+        .expect("serialized json never contains NUL")
+        .into_raw()
+}
+
+/// Solve `day` of the default year using the `len` bytes at `input_ptr`.
+///
+/// On return, `*out_json_buf` points to either the solution, as the same JSON
+/// printed by `aoc run --json`, or to `{"error": {"kind": ..., "message": ...}}`.
+/// Either way it must be released with `aoc_free_string`. If `out_json_buf` is
+/// null, `AOC_STATUS_NULL_POINTER` is returned and nothing is written.
+///
+/// # Safety
+///
+/// `input_ptr` must be valid for reads of `len` bytes (it may be null if `len`
+/// is zero), and `out_json_buf` must be null or valid for writing a pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_solve(
+    day: u32,
+    input_ptr: *const u8,
+    len: usize,
+    out_json_buf: *mut *mut c_char,
+) -> AocStatus {
+    if out_json_buf.is_null() {
+        return AocStatus::NullPointer;
+    }
+
+    let (status, json) = if len == 0 {
+        solve_json(day as usize, &[])
+    } else if input_ptr.is_null() {
+        AocStatus::NullPointer.error("input is null")
+    } else {
+        // SAFETY: non-null, and the caller guarantees `len` readable bytes
+        // that outlive this call.
+        let input = unsafe { std::slice::from_raw_parts(input_ptr, len) };
+        solve_json(day as usize, input)
+    };
+
+    // SAFETY: checked for null above, and the caller guarantees it's writable.
+    unsafe { *out_json_buf = into_raw(json) };
+
+    status
+}
+
+/// The implemented days, as a JSON array of `{"year", "day", "label"}`.
+///
+/// The result must be released with `aoc_free_string`.
+#[unsafe(no_mangle)]
+pub extern "C" fn aoc_days() -> *mut c_char {
+    into_raw(days().to_string())
+}
+
+/// Release a string returned by this library. Passing null is a no-op.
+///
+/// # Safety
+///
+/// `s` must be null or a pointer previously returned by this library that has
+/// not already been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aoc_free_string(s: *mut c_char) {
+    if s.is_null() {
+        return;
+    }
+
+    // SAFETY: the caller guarantees this came from `CString::into_raw` in
+    // `into_raw` and is only freed once.
+    drop(unsafe { CString::from_raw(s) });
+}
@@ -0,0 +1,165 @@
+//! Load the built shared library and drive it the way a C caller would.
+use std::{
+    ffi::{CStr, c_char},
+    path::{Path, PathBuf},
+    ptr,
+};
+
+use aoc_ffi::AocStatus;
+use libloading::{Library, Symbol};
+use serde_json::{Value, json};
+
+type SolveFn = unsafe extern "C" fn(u32, *const u8, usize, *mut *mut c_char) -> AocStatus;
+type DaysFn = extern "C" fn() -> *mut c_char;
+type FreeFn = unsafe extern "C" fn(*mut c_char);
+
+const EXAMPLES: &[(u32, &str, (i64, i64))] = &[
+    (1, "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82", (3, 6)),
+    (
+        2,
+        "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124",
+        (1227775554, 4174379265),
+    ),
+    (
+        3,
+        "987654321111111\n811111111111119\n234234234234278\n818181911112111",
+        (357, 3121910778619),
+    ),
+    (
+        4,
+        "..@@.@@@@.\n@@@.@.@.@@\n@@@@@.@.@@\n@.@@@@..@.\n@@.@@@@.@@\n.@@@@@@@.@\n.@.@.@.@@@\n@.@@@.@@@@\n.@@@@@@@@.\n@.@.@@@.@.",
+        (13, 43),
+    ),
+    (
+        5,
+        "3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32",
+        (3, 14),
+    ),
+    (
+        6,
+        "123 328  51 64\n 45 64  387 23\n  6 98  215 314\n*   +   *   +  ",
+        (4277556, 3263827),
+    ),
+    (
+        7,
+        ".......S.......\n...............\n.......^.......\n...............\n......^.^......\n...............\n.....^.^.^.....\n...............\n....^.^...^....\n...............\n...^.^...^.^...\n...............\n..^...^.....^..\n...............\n.^.^.^.^.^...^.\n...............",
+        (21, 40),
+    ),
+    (9, "7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3", (50, 24)),
+    (
+        10,
+        "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}\n[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}\n[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}",
+        (7, 33),
+    ),
+];
+
+/// Where cargo builds the cdylib, as worked out by the build script.
+fn library_path() -> PathBuf {
+    Path::new(env!("AOC_FFI_LIB_DIR")).join(format!(
+        "{}aoc_ffi{}",
+        std::env::consts::DLL_PREFIX,
+        std::env::consts::DLL_SUFFIX
+    ))
+}
+
+struct Api<'lib> {
+    solve: Symbol<'lib, SolveFn>,
+    days: Symbol<'lib, DaysFn>,
+    free: Symbol<'lib, FreeFn>,
+}
+
+impl<'lib> Api<'lib> {
+    fn load(lib: &'lib Library) -> Self {
+        // SAFETY: the signatures match the definitions in src/lib.rs
+        unsafe {
+            Self {
+                solve: lib.get(b"aoc_solve").unwrap(),
+                days: lib.get(b"aoc_days").unwrap(),
+                free: lib.get(b"aoc_free_string").unwrap(),
+            }
+        }
+    }
+
+    fn take(&self, s: *mut c_char) -> Value {
+        assert!(!s.is_null());
+        // SAFETY: the library returned a valid NUL-terminated string, which we
+        // free exactly once after copying it.
+        let json = unsafe { CStr::from_ptr(s) }.to_str().unwrap().to_string();
+        unsafe { (self.free)(s) };
+        serde_json::from_str(&json).unwrap()
+    }
+
+    fn solve(&self, day: u32, input: &[u8]) -> (AocStatus, Value) {
+        let mut out = ptr::null_mut();
+        // SAFETY: `input` is valid for its length and `out` is writable.
+        let status = unsafe { (self.solve)(day, input.as_ptr(), input.len(), &mut out) };
+        (status, self.take(out))
+    }
+}
+
+fn library() -> Library {
+    let path = library_path();
+    // SAFETY: loading our own library runs no untrusted initializers
+    unsafe { Library::new(&path) }.unwrap_or_else(|e| {
+        panic!(
+            "could not load the cdylib from {}, it should be built alongside the tests: {}",
+            path.display(),
+            e
+        )
+    })
+}
+
+#[test]
+fn examples() {
+    let lib = library();
+    let api = Api::load(&lib);
+
+    for (day, input, (one, two)) in EXAMPLES {
+        let (status, solution) = api.solve(*day, input.as_bytes());
+        assert_eq!(status, AocStatus::Ok, "day {}: {}", day, solution);
+        assert_eq!(
+            solution,
+            json!({ "part_one": one, "part_two": two }),
+            "day {}",
+            day
+        );
+    }
+}
+
+#[test]
+fn days() {
+    let lib = library();
+    let api = Api::load(&lib);
+
+    let days = api.take((api.days)());
+    let days = days.as_array().unwrap();
+    assert_eq!(days.len(), 12);
+    assert_eq!(
+        days[0],
+        json!({ "year": 2025, "day": 1, "label": "001 secret entrance" })
+    );
+}
+
+#[test]
+fn errors() {
+    let lib = library();
+    let api = Api::load(&lib);
+
+    let (status, body) = api.solve(26, b"");
+    assert_eq!(status, AocStatus::NotImplemented);
+    assert_eq!(body["error"]["kind"], "not_implemented");
+
+    let (status, _) = api.solve(1, &[0xff, 0xfe]);
+    assert_eq!(status, AocStatus::InvalidInput);
+
+    let (status, body) = api.solve(1, b"L");
+    assert_eq!(status, AocStatus::SolveFailed);
+    assert_eq!(body["error"]["kind"], "solve_failed");
+
+    // SAFETY: a null out pointer must be rejected without being written
+    let status = unsafe { (api.solve)(1, b"L1".as_ptr(), 2, ptr::null_mut()) };
+    assert_eq!(status, AocStatus::NullPointer);
+
+    // SAFETY: freeing null is documented as a no-op
+    unsafe { (api.free)(ptr::null_mut()) };
+}
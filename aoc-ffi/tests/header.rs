@@ -0,0 +1,31 @@
+//! Make sure the checked in header matches the current API.
+//!
+//! Set `UPDATE_HEADER=1` to regenerate it instead.
+use std::path::Path;
+
+#[test]
+fn header_is_up_to_date() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).unwrap();
+
+    let mut generated = Vec::new();
+    cbindgen::Builder::new()
+        .with_crate(crate_dir)
+        .with_config(config)
+        .generate()
+        .expect("Unable to generate the header")
+        .write(&mut generated);
+
+    let generated = String::from_utf8(generated).unwrap();
+    let header = crate_dir.join("include/aoc.h");
+    if std::env::var_os("UPDATE_HEADER").is_some() {
+        std::fs::write(&header, &generated).unwrap();
+        return;
+    }
+
+    let checked_in = std::fs::read_to_string(&header).unwrap_or_default();
+    assert_eq!(
+        generated, checked_in,
+        "include/aoc.h is stale, run `just ffi-header`"
+    );
+}
@@ -1,3 +1,22 @@
 pub mod problem;
+#[cfg(feature = "trace")]
+pub mod trace;
+pub mod viz;
 
-pub use problem::{Problem, Solution};
+pub use problem::{DEFAULT_YEAR, DynAnswer, DynSolution, Problem, Solution};
+pub use viz::Visualize;
+
+/// Emit a structured event from a solver, see `aoc_plumbing::trace`.
+///
+/// This is the no-op used without the `trace` feature. The values are still
+/// borrowed (but never evaluated at runtime), so variables that only exist for
+/// tracing don't trigger unused warnings.
+#[cfg(not(feature = "trace"))]
+#[macro_export]
+macro_rules! trace {
+    ($event:literal $(, $key:ident = $value:expr)* $(,)?) => {
+        if false {
+            $(let _ = &$value;)*
+        }
+    };
+}
@@ -1,10 +1,11 @@
 use std::{fmt::Display, str::FromStr};
 
-use serde::Serialize;
+use serde::{Serialize, Serializer};
 
-/// This struct enables printing a given solution in either plaintext or JSON,
-/// depending on the presence of the `AOC_OUTPUT_JSON` ENV var. Its main purpose
-/// is to standardize the output for consuption by the CI system.
+/// This struct enables printing a given solution in either plaintext or JSON
+/// (the cli picks based on its `output` config, or the `AOC_OUTPUT_JSON` /
+/// `AOC_JSON` ENV vars). Its main purpose is to standardize the output for
+/// consuption by the CI system.
 ///
 /// # Usage
 ///
@@ -23,7 +24,7 @@ use serde::Serialize;
 ///     "{\"part_one\":\"hello world\",\"part_two\":12345}".to_string()
 /// );
 /// ```
-#[derive(Debug, Serialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, PartialEq)]
 pub struct Solution<T, G>
 where
     T: Display + Serialize + PartialEq,
@@ -77,7 +78,114 @@ where
     }
 }
 
+impl<T, G> Solution<T, G>
+where
+    T: Display + Serialize + PartialEq,
+    G: Display + Serialize + PartialEq,
+{
+    /// Erase the types of both parts, for when many different problems need to
+    /// be handled uniformly.
+    ///
+    /// ```
+    /// use aoc_plumbing::{DynAnswer, Solution};
+    /// let s = Solution::new("hello world", 12345).to_dyn().unwrap();
+    ///
+    /// assert_eq!(s.to_string(), "part 1: hello world\npart 2: 12345");
+    /// assert_eq!(s.part_one, DynAnswer::Text("hello world".into()));
+    /// assert_eq!(
+    ///     serde_json::to_string(&s).unwrap(),
+    ///     "{\"part_one\":\"hello world\",\"part_two\":12345}".to_string()
+    /// );
+    ///
+    /// // answers wider than a u64 are kept exactly
+    /// let s = Solution::new(u128::MAX, -(1_i128 << 100)).to_dyn().unwrap();
+    /// assert_eq!(s.part_one, DynAnswer::Number(u128::MAX.to_string()));
+    /// assert_eq!(
+    ///     serde_json::to_string(&s).unwrap(),
+    ///     format!("{{\"part_one\":{},\"part_two\":{}}}", u128::MAX, -(1_i128 << 100))
+    /// );
+    /// ```
+    pub fn to_dyn(&self) -> Result<DynSolution, serde_json::Error> {
+        Ok(Solution::new(
+            DynAnswer::new(&self.part_one)?,
+            DynAnswer::new(&self.part_two)?,
+        ))
+    }
+}
+
+/// A type-erased answer to one part of a problem.
+///
+/// Answers are kept as text, tagged with whether they were a number or not, so
+/// integers of any width survive (a `serde_json::Value` can't hold a `u128`
+/// past `u64::MAX`). This serializes exactly like the original answer would
+/// have, and displays like it as well (strings are not quoted).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DynAnswer {
+    /// A number, as its JSON representation.
+    Number(String),
+    /// Anything else, as it displays.
+    Text(String),
+}
+
+impl DynAnswer {
+    pub fn new<T>(answer: &T) -> Result<Self, serde_json::Error>
+    where
+        T: Display + Serialize + ?Sized,
+    {
+        // serializing straight to a string, unlike to a `Value`, handles 128 bit
+        // integers
+        let json = serde_json::to_string(answer)?;
+        Ok(match json.as_bytes().first() {
+            Some(b'-' | b'0'..=b'9') => Self::Number(json),
+            Some(b'"') => Self::Text(serde_json::from_str(&json)?),
+            _ => Self::Text(answer.to_string()),
+        })
+    }
+}
+
+impl Serialize for DynAnswer {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Text(s) => serializer.serialize_str(s),
+            Self::Number(n) => {
+                if let Ok(v) = n.parse::<u64>() {
+                    serializer.serialize_u64(v)
+                } else if let Ok(v) = n.parse::<i64>() {
+                    serializer.serialize_i64(v)
+                } else if let Ok(v) = n.parse::<u128>() {
+                    serializer.serialize_u128(v)
+                } else if let Ok(v) = n.parse::<i128>() {
+                    serializer.serialize_i128(v)
+                } else if let Ok(v) = n.parse::<f64>() {
+                    serializer.serialize_f64(v)
+                } else {
+                    serializer.serialize_str(n)
+                }
+            }
+        }
+    }
+}
+
+impl Display for DynAnswer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(s) | Self::Text(s) => f.write_str(s),
+        }
+    }
+}
+
+/// A `Solution` for any `Problem`.
+pub type DynSolution = Solution<DynAnswer, DynAnswer>;
+
+/// The year assumed for problems that do not specify one.
+pub const DEFAULT_YEAR: usize = 2025;
+
 pub trait Problem: FromStr {
+    /// The event year, so several years of problems can coexist.
+    const YEAR: usize = DEFAULT_YEAR;
     const DAY: usize;
     const TITLE: &'static str;
     const README: &'static str;
@@ -110,6 +218,15 @@ pub trait Problem: FromStr {
         format!("{:03}", <Self as Problem>::DAY)
     }
 
+    /// Like `problem_label`, but unambiguous across years.
+    fn qualified_label() -> String {
+        format!(
+            "{} {}",
+            <Self as Problem>::YEAR,
+            <Self as Problem>::problem_label()
+        )
+    }
+
     fn long_description() -> String {
         format!(
             "{} {}",
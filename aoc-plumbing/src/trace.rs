@@ -0,0 +1,126 @@
+//! Structured events from inside a solver, for explaining how it got its
+//! answer.
+//!
+//! Solvers emit events with [`trace!`](crate::trace!). Without the `trace`
+//! feature the macro expands to nothing, so benchmarks never pay for it. With
+//! the feature, events are only recorded inside [`collect`], and the values in
+//! an event aren't even evaluated otherwise.
+//!
+//! ```
+//! use aoc_plumbing::trace::{self, Event};
+//!
+//! fn solve(input: &[i64]) -> i64 {
+//!     let mut sum = 0;
+//!     for (idx, v) in input.iter().enumerate() {
+//!         sum += v;
+//!         aoc_plumbing::trace!("step", idx = idx, sum = sum);
+//!     }
+//!     sum
+//! }
+//!
+//! // nothing is recorded outside of `collect`
+//! assert_eq!(solve(&[1, 2]), 3);
+//!
+//! let (sum, events) = trace::collect(|| solve(&[3, 4]));
+//! assert_eq!(sum, 7);
+//! assert_eq!(events.len(), 2);
+//! assert_eq!(events[1].event, "step");
+//! assert_eq!(
+//!     serde_json::to_string(&events[1]).unwrap(),
+//!     r#"{"event":"step","idx":1,"sum":7}"#
+//! );
+//!
+//! // fields are kept as json text, so even 128 bit integers survive
+//! let (_, events) = trace::collect(|| aoc_plumbing::trace!("big", v = u128::MAX));
+//! assert_eq!(events[0].fields[0].1.get(), u128::MAX.to_string());
+//! ```
+use std::cell::RefCell;
+
+use serde::{Serialize, ser::SerializeMap};
+use serde_json::value::RawValue;
+
+/// A single named event and its fields, serialized as one flat JSON object
+/// with the fields in the order they were given.
+#[derive(Debug, Clone)]
+pub struct Event {
+    pub event: &'static str,
+    pub fields: Vec<(&'static str, Box<RawValue>)>,
+}
+
+impl PartialEq for Event {
+    fn eq(&self, other: &Self) -> bool {
+        self.event == other.event
+            && self.fields.len() == other.fields.len()
+            && self
+                .fields
+                .iter()
+                .zip(&other.fields)
+                .all(|((k1, v1), (k2, v2))| k1 == k2 && v1.get() == v2.get())
+    }
+}
+
+impl Serialize for Event {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.fields.len() + 1))?;
+        map.serialize_entry("event", self.event)?;
+        for (key, value) in &self.fields {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+thread_local! {
+    static SINK: RefCell<Option<Vec<Event>>> = const { RefCell::new(None) };
+}
+
+/// Run `f`, returning its result along with every event it emitted.
+///
+/// Events are recorded per thread, so anything a solver emits from other
+/// threads is not captured.
+pub fn collect<T>(f: impl FnOnce() -> T) -> (T, Vec<Event>) {
+    let outer = SINK.with(|sink| sink.replace(Some(Vec::default())));
+    let res = f();
+    let events = SINK.with(|sink| sink.replace(outer)).unwrap_or_default();
+    (res, events)
+}
+
+/// Whether anything is currently collecting events on this thread.
+pub fn enabled() -> bool {
+    SINK.with(|sink| sink.borrow().is_some())
+}
+
+#[doc(hidden)]
+pub fn emit(event: &'static str, fields: Vec<(&'static str, Box<RawValue>)>) {
+    SINK.with(|sink| {
+        if let Some(events) = sink.borrow_mut().as_mut() {
+            events.push(Event { event, fields });
+        }
+    });
+}
+
+#[doc(hidden)]
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> Box<RawValue> {
+    // this only fails for things that can't be json at all, like maps with
+    // non-string keys
+    serde_json::value::to_raw_value(value).unwrap_or_else(|_| {
+        RawValue::from_string("null".to_string()).expect("null is valid json")
+    })
+}
+
+/// Emit an event with the given name and `key = value` fields, where every
+/// value is `Serialize`.
+#[macro_export]
+macro_rules! trace {
+    ($event:literal $(, $key:ident = $value:expr)* $(,)?) => {
+        if $crate::trace::enabled() {
+            $crate::trace::emit(
+                $event,
+                vec![$((stringify!($key), $crate::trace::to_value(&$value))),*],
+            );
+        }
+    };
+}
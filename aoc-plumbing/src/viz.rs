@@ -0,0 +1,134 @@
+//! Optional visualizations for problems that lend themselves to one.
+//!
+//! A problem opts in by implementing [`Visualize`], producing a [`Scene`] of
+//! simple shapes from its input. Scenes are rendered by [`svg::render`] or
+//! [`ansi::render`], so problems never deal with output formats directly.
+//!
+//! ```
+//! use aoc_plumbing::viz::{Paint, Scene, ansi, svg};
+//!
+//! let mut scene = Scene::new(4.0, 2.0);
+//! scene.cell(0, 0, Paint::Primary);
+//! scene.line((0.5, 1.5), (3.5, 1.5), Paint::Highlight);
+//! scene.legend(Paint::Primary, "the start");
+//!
+//! assert!(svg::render(&scene).starts_with("<svg"));
+//! assert_eq!(ansi::render(&scene, 80, false), "█   \n····\n█ the start\n");
+//! ```
+use crate::Problem;
+
+pub mod ansi;
+pub mod svg;
+
+/// A problem that can be drawn.
+///
+/// This is deliberately separate from solving, so visualizing never costs the
+/// solutions anything. Implementations are free to recompute whatever they
+/// need from the input.
+pub trait Visualize: Problem {
+    fn visualize(raw_input: &str) -> Result<Scene, Self::ProblemError>;
+}
+
+/// A small, fixed palette, so every renderer can map it to something sensible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Paint {
+    Muted,
+    Primary,
+    Secondary,
+    Highlight,
+    Alert,
+}
+
+impl Paint {
+    pub fn hex(&self) -> &'static str {
+        match self {
+            Self::Muted => "#6e7681",
+            Self::Primary => "#3fb950",
+            Self::Secondary => "#58a6ff",
+            Self::Highlight => "#f2cc60",
+            Self::Alert => "#f85149",
+        }
+    }
+
+    pub fn ansi(&self) -> &'static str {
+        match self {
+            Self::Muted => "90",
+            Self::Primary => "32",
+            Self::Secondary => "34",
+            Self::Highlight => "33",
+            Self::Alert => "31",
+        }
+    }
+}
+
+/// A point in scene coordinates, with `y` increasing downwards.
+pub type Coord = (f64, f64);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    /// The unit square with its top left corner at `(x, y)`.
+    Cell {
+        x: usize,
+        y: usize,
+        paint: Paint,
+    },
+    Line {
+        from: Coord,
+        to: Coord,
+        paint: Paint,
+    },
+    /// A closed outline.
+    Polygon {
+        points: Vec<Coord>,
+        paint: Paint,
+    },
+    Dot {
+        at: Coord,
+        paint: Paint,
+    },
+}
+
+/// Shapes on a `width` x `height` canvas, drawn in order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Scene {
+    pub width: f64,
+    pub height: f64,
+    pub shapes: Vec<Shape>,
+    pub legend: Vec<(Paint, String)>,
+}
+
+impl Scene {
+    pub fn new(width: f64, height: f64) -> Self {
+        Self {
+            width,
+            height,
+            ..Default::default()
+        }
+    }
+
+    pub fn cell(&mut self, x: usize, y: usize, paint: Paint) {
+        self.shapes.push(Shape::Cell { x, y, paint });
+    }
+
+    pub fn line(&mut self, from: Coord, to: Coord, paint: Paint) {
+        self.shapes.push(Shape::Line { from, to, paint });
+    }
+
+    pub fn polygon(&mut self, points: Vec<Coord>, paint: Paint) {
+        self.shapes.push(Shape::Polygon { points, paint });
+    }
+
+    /// The outline of the rectangle with the given opposite corners.
+    pub fn rect(&mut self, a: Coord, b: Coord, paint: Paint) {
+        self.polygon(vec![a, (b.0, a.1), b, (a.0, b.1)], paint);
+    }
+
+    pub fn dot(&mut self, at: Coord, paint: Paint) {
+        self.shapes.push(Shape::Dot { at, paint });
+    }
+
+    /// Explain what a paint means in this scene.
+    pub fn legend(&mut self, paint: Paint, label: impl Into<String>) {
+        self.legend.push((paint, label.into()));
+    }
+}
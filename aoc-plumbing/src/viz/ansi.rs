@@ -0,0 +1,135 @@
+//! Render a [`Scene`] as text for a terminal.
+use super::{Coord, Paint, Scene, Shape};
+
+const CELL: char = '█';
+const LINE: char = '·';
+const DOT: char = 'o';
+
+/// Render `scene` at most `max_width` characters wide, optionally colored with
+/// ANSI escapes.
+///
+/// Scenes that fit are drawn at one character per unit, which keeps grids
+/// exact. Larger scenes are scaled down, halving the vertical scale to account
+/// for characters being roughly twice as tall as they are wide.
+///
+/// ```
+/// use aoc_plumbing::viz::{Paint, Scene, ansi};
+///
+/// let mut scene = Scene::new(200.0, 100.0);
+/// scene.dot((100.0, 50.0), Paint::Alert);
+/// let out = ansi::render(&scene, 20, false);
+/// assert_eq!(out.lines().count(), 5);
+/// assert_eq!(out.lines().nth(2), Some("          o         "));
+/// ```
+pub fn render(scene: &Scene, max_width: usize, color: bool) -> String {
+    let mut canvas = Canvas::new(scene, max_width.max(1));
+
+    for shape in &scene.shapes {
+        match shape {
+            Shape::Cell { x, y, paint } => canvas.cell(*x, *y, *paint),
+            Shape::Line { from, to, paint } => canvas.line(*from, *to, *paint),
+            Shape::Polygon { points, paint } => {
+                for (idx, from) in points.iter().enumerate() {
+                    let to = points[(idx + 1) % points.len()];
+                    canvas.line(*from, to, *paint);
+                }
+            }
+            Shape::Dot { at, paint } => canvas.plot(*at, DOT, *paint),
+        }
+    }
+
+    let paint = |ch: char, paint: Paint| {
+        if color {
+            format!("\x1b[{}m{}\x1b[0m", paint.ansi(), ch)
+        } else {
+            ch.to_string()
+        }
+    };
+
+    let mut out = String::new();
+    for row in &canvas.chars {
+        for ch in row {
+            match ch {
+                Some((ch, p)) => out.push_str(&paint(*ch, *p)),
+                None => out.push(' '),
+            }
+        }
+        out.push('\n');
+    }
+
+    for (p, label) in &scene.legend {
+        out.push_str(&paint(CELL, *p));
+        out.push(' ');
+        out.push_str(label);
+        out.push('\n');
+    }
+
+    out
+}
+
+struct Canvas {
+    chars: Vec<Vec<Option<(char, Paint)>>>,
+    scale_x: f64,
+    scale_y: f64,
+}
+
+impl Canvas {
+    fn new(scene: &Scene, max_width: usize) -> Self {
+        let (scale_x, scale_y) = if scene.width <= max_width as f64 {
+            (1.0, 1.0)
+        } else {
+            let scale = max_width as f64 / scene.width;
+            (scale, scale / 2.0)
+        };
+
+        let cols = ((scene.width * scale_x).ceil() as usize).max(1);
+        let rows = ((scene.height * scale_y).ceil() as usize).max(1);
+
+        Self {
+            chars: vec![vec![None; cols]; rows],
+            scale_x,
+            scale_y,
+        }
+    }
+
+    fn plot(&mut self, at: Coord, ch: char, paint: Paint) {
+        let col = (at.0 * self.scale_x).floor();
+        let row = (at.1 * self.scale_y).floor();
+        if col < 0.0 || row < 0.0 {
+            return;
+        }
+
+        // points on the far edges belong to the last row/column
+        let row = (row as usize).min(self.chars.len() - 1);
+        let col = (col as usize).min(self.chars[row].len() - 1);
+        self.chars[row][col] = Some((ch, paint));
+    }
+
+    fn cell(&mut self, x: usize, y: usize, paint: Paint) {
+        let span = |start: usize, scale: f64| {
+            let lo = (start as f64 * scale).floor() as usize;
+            let hi = ((start + 1) as f64 * scale).floor() as usize;
+            lo..hi.max(lo + 1)
+        };
+
+        for row in span(y, self.scale_y) {
+            for col in span(x, self.scale_x) {
+                if let Some(ch) = self.chars.get_mut(row).and_then(|r| r.get_mut(col)) {
+                    *ch = Some((CELL, paint));
+                }
+            }
+        }
+    }
+
+    fn line(&mut self, from: Coord, to: Coord, paint: Paint) {
+        let dx = (to.0 - from.0) * self.scale_x;
+        let dy = (to.1 - from.1) * self.scale_y;
+        let steps = (dx.abs().max(dy.abs()) * 2.0).ceil().max(1.0) as usize;
+
+        for step in 0..=steps {
+            let t = step as f64 / steps as f64;
+            let at = (from.0 + (to.0 - from.0) * t, from.1 + (to.1 - from.1) * t);
+            self.plot(at, LINE, paint);
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Render a [`Scene`] as a standalone SVG document.
+use std::fmt::Write;
+
+use super::{Coord, Scene, Shape};
+
+const BACKGROUND: &str = "#0d1117";
+
+/// Render `scene`, scaled so the longest side is at most 1000px.
+///
+/// ```
+/// use aoc_plumbing::viz::{Paint, Scene, svg};
+///
+/// let mut scene = Scene::new(10.0, 5.0);
+/// scene.cell(1, 2, Paint::Alert);
+/// let out = svg::render(&scene);
+/// assert!(out.contains(r#"viewBox="0 0 10 5""#));
+/// assert!(out.contains(r##"<rect x="1" y="2" width="1" height="1" fill="#f85149"/>"##));
+/// ```
+pub fn render(scene: &Scene) -> String {
+    let longest = scene.width.max(scene.height).max(1.0);
+    let scale = 1000.0 / longest;
+    // sizes that should look the same regardless of the scene's coordinates
+    let unit = longest / 250.0;
+
+    let mut out = String::new();
+    // writing to a string can't fail
+    let _ = writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {} {}" width="{:.0}" height="{:.0}">"#,
+        scene.width,
+        scene.height,
+        scene.width * scale,
+        scene.height * scale
+    );
+    let _ = writeln!(
+        out,
+        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+        BACKGROUND
+    );
+
+    for shape in &scene.shapes {
+        let _ = match shape {
+            Shape::Cell { x, y, paint } => writeln!(
+                out,
+                r#"<rect x="{}" y="{}" width="1" height="1" fill="{}"/>"#,
+                x,
+                y,
+                paint.hex()
+            ),
+            Shape::Line { from, to, paint } => writeln!(
+                out,
+                r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="1.5" vector-effect="non-scaling-stroke"/>"#,
+                from.0,
+                from.1,
+                to.0,
+                to.1,
+                paint.hex()
+            ),
+            Shape::Polygon { points, paint } => writeln!(
+                out,
+                r#"<polygon points="{}" fill="none" stroke="{}" stroke-width="1.5" vector-effect="non-scaling-stroke"/>"#,
+                points_attr(points),
+                paint.hex()
+            ),
+            Shape::Dot { at, paint } => writeln!(
+                out,
+                r#"<circle cx="{}" cy="{}" r="{:.3}" fill="{}"/>"#,
+                at.0,
+                at.1,
+                unit,
+                paint.hex()
+            ),
+        };
+    }
+
+    for (idx, (paint, label)) in scene.legend.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            r#"<text x="{:.3}" y="{:.3}" font-size="{:.3}" font-family="monospace" fill="{}">{}</text>"#,
+            unit * 2.0,
+            unit * 6.0 * (idx + 1) as f64,
+            unit * 5.0,
+            paint.hex(),
+            escape(label)
+        );
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+fn points_attr(points: &[Coord]) -> String {
+    points
+        .iter()
+        .map(|(x, y)| format!("{},{}", x, y))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
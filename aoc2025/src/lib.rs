@@ -0,0 +1,257 @@
+//! Every 2025 solution behind a single library.
+//!
+//! Each day is re-exported both as its crate and as its problem type, and can
+//! be solved by number with [`solve`]. Every day has a `day-NNN` feature, all
+//! of which are enabled by the default `all` feature, so depending on just a
+//! few days looks like:
+//!
+//! ```toml
+//! aoc2025 = { path = "../aoc2025", default-features = false, features = ["day-001"] }
+//! ```
+//!
+//! This is also the one place days are registered: the cli and the C ABI are
+//! generated from [`registry!`].
+pub use aoc_plumbing::{
+    DynAnswer, DynSolution, Problem, Solution,
+    viz::{Scene, Visualize},
+};
+use serde::Serialize;
+
+/// The year of every problem in this crate.
+pub const YEAR: usize = 2025;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("day {0} is not available (is the day-{0:03} feature enabled?)")]
+    NotAvailable(usize),
+    #[error("day {0} has no visualization")]
+    NoVisualization(usize),
+    #[error("failed to solve day {day}")]
+    Solve {
+        day: usize,
+        #[source]
+        source: anyhow::Error,
+    },
+    #[error("failed to visualize day {day}")]
+    Visualize {
+        day: usize,
+        #[source]
+        source: anyhow::Error,
+    },
+    #[error("failed to serialize the solution for day {day}")]
+    Serialize {
+        day: usize,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// A description of an available problem.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProblemInfo {
+    pub year: usize,
+    pub day: usize,
+    pub label: String,
+    pub title: &'static str,
+    pub readme: &'static str,
+}
+
+impl ProblemInfo {
+    pub fn of<T: Problem>() -> Self {
+        Self {
+            year: T::YEAR,
+            day: T::DAY,
+            label: T::problem_label(),
+            title: T::TITLE,
+            readme: T::README,
+        }
+    }
+}
+
+/// Invoke `$callback! { (Problem, krate, "feature", year, day), ... }` with
+/// every day in this crate, followed by anything given after a `;`.
+///
+/// Days whose problem implements [`Visualize`] have a trailing `Visualize`,
+/// like `(PrintingDepartment, printing_department, "day-004", 2025, 4,
+/// Visualize)`, which is how [`visualize`] finds them.
+///
+/// The problem types must be in scope where this is expanded, which
+/// `use aoc2025::problems::*` takes care of. Since the callback can't check
+/// which days are enabled, callers outside this crate need every day enabled
+/// (the default `all` feature).
+///
+/// ```
+/// use aoc2025::problems::*;
+///
+/// macro_rules! days {
+///     ($(($name:ident, $krate:ident, $feature:literal, $year:literal, $day:literal $(, $viz:ident)?)),* $(,)?) => {
+///         [$((stringify!($name), $day)),*]
+///     };
+/// }
+///
+/// let days = aoc2025::registry!(days);
+/// assert_eq!(days[0], ("SecretEntrance", 1));
+///
+/// // more entries can be tacked on the end
+/// let days = aoc2025::registry!(days; (SecretEntrance, secret_entrance, "", 2024, 1));
+/// assert_eq!(days.last(), Some(&("SecretEntrance", 1)));
+/// assert_eq!(days.len(), aoc2025::problems().len() + 1);
+/// ```
+#[macro_export]
+macro_rules! registry {
+    ($callback:ident $(; $($extra:tt)*)?) => {
+        $callback! {
+            (SecretEntrance, secret_entrance, "day-001", 2025, 1),
+            (GiftShop, gift_shop, "day-002", 2025, 2),
+            (Lobby, lobby, "day-003", 2025, 3),
+            (PrintingDepartment, printing_department, "day-004", 2025, 4, Visualize),
+            (Cafeteria, cafeteria, "day-005", 2025, 5),
+            (TrashCompactor, trash_compactor, "day-006", 2025, 6),
+            (Laboratories, laboratories, "day-007", 2025, 7, Visualize),
+            (Playground, playground, "day-008", 2025, 8, Visualize),
+            (MovieTheater, movie_theater, "day-009", 2025, 9, Visualize),
+            (Factory, factory, "day-010", 2025, 10),
+            (Reactor, reactor, "day-011", 2025, 11),
+            (ChristmasTreeFarm, christmas_tree_farm, "day-012", 2025, 12),
+            // facade_marker
+            $($($extra)*)?
+        }
+    };
+}
+
+macro_rules! facade {
+    ($(($name:ident, $krate:ident, $feature:literal, $year:literal, $day:literal $(, $viz:ident)?)),* $(,)?) => {
+        $(
+        #[cfg(feature = $feature)]
+        pub use $krate::{self, $name};
+
+        #[cfg(feature = $feature)]
+        const _: () = assert!(
+            <$name as Problem>::YEAR == $year && $year == YEAR && <$name as Problem>::DAY == $day,
+            concat!("mismatched registration for ", stringify!($name))
+        );
+        )*
+
+        /// Just the problem types, for bringing into scope with a glob import.
+        pub mod problems {
+            $(
+            #[cfg(feature = $feature)]
+            pub use $krate::$name;
+            )*
+        }
+
+        /// Solve the given day with the given input.
+        ///
+        /// The answers are type-erased, serializing the same way as those of
+        /// the underlying problem.
+        #[allow(unused_variables)] // when no days are enabled
+        pub fn solve(day: usize, input: &str) -> Result<DynSolution> {
+            match day {
+                $(
+                #[cfg(feature = $feature)]
+                $day => _solve::<$name>(input),
+                )*
+                _ => Err(Error::NotAvailable(day)),
+            }
+        }
+
+        /// Visualize the given day's input, for the days that can be.
+        #[allow(unused_variables)] // when no days are enabled
+        pub fn visualize(day: usize, input: &str) -> Result<Scene> {
+            match day {
+                $($(
+                #[cfg(feature = $feature)]
+                $day => <$name as $viz>::visualize(input).map_err(|e| Error::Visualize {
+                    day,
+                    source: e.into(),
+                }),
+                )?)*
+                _ if problems().iter().any(|p| p.day == day) => {
+                    Err(Error::NoVisualization(day))
+                }
+                _ => Err(Error::NotAvailable(day)),
+            }
+        }
+
+        /// Every available problem, in order.
+        pub fn problems() -> Vec<ProblemInfo> {
+            vec![
+                $(
+                #[cfg(feature = $feature)]
+                ProblemInfo::of::<$name>(),
+                )*
+            ]
+        }
+    };
+}
+
+fn _solve<T>(input: &str) -> Result<DynSolution>
+where
+    T: Problem,
+    <T as Problem>::ProblemError: Into<anyhow::Error>,
+{
+    let day = T::DAY;
+    let solution = T::solve(input).map_err(|e| Error::Solve {
+        day,
+        source: e.into(),
+    })?;
+
+    solution
+        .to_dyn()
+        .map_err(|source| Error::Serialize { day, source })
+}
+
+registry!(facade);
+
+#[cfg(all(test, feature = "all"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let input = "L68
+L30
+R48
+L5
+R60
+L55
+L1
+L99
+R14
+L82";
+        let solution = solve(1, input).unwrap();
+        assert_eq!(solution.to_string(), "part 1: 3\npart 2: 6");
+        assert_eq!(
+            serde_json::to_string(&solution).unwrap(),
+            r#"{"part_one":3,"part_two":6}"#
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(solve(26, ""), Err(Error::NotAvailable(26))));
+        assert!(matches!(solve(1, "L"), Err(Error::Solve { day: 1, .. })));
+    }
+
+    #[test]
+    fn listing() {
+        let problems = problems();
+        assert_eq!(problems.len(), 12);
+        assert!(problems.iter().enumerate().all(|(i, p)| p.day == i + 1));
+        assert_eq!(problems[2].label, "003 lobby");
+    }
+
+    #[test]
+    fn visualizing() {
+        let scene = visualize(9, "7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3").unwrap();
+        assert_eq!((scene.width, scene.height), (9.0, 6.0));
+        assert!(matches!(visualize(1, ""), Err(Error::NoVisualization(1))));
+        assert!(matches!(visualize(26, ""), Err(Error::NotAvailable(26))));
+        assert!(matches!(
+            visualize(9, "x"),
+            Err(Error::Visualize { day: 9, .. })
+        ));
+    }
+}
@@ -16,6 +16,7 @@ cargo check --all-targets
 
 # run the unit tests
 cargo test
+just test-trace
 
 # run integration tests
 just test
@@ -1,39 +1,332 @@
-use std::str::FromStr;
+use std::{io::BufRead, str::FromStr};
 
+use anyhow::{Context, anyhow, bail};
 use aoc_plumbing::Problem;
+use serde::Serialize;
 
-#[derive(Debug, Clone)]
-pub struct SecretEntrance {
-    zeros: i64,
-    pass_zeros: i64,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum Direction {
+    #[serde(rename = "L")]
+    Left,
+    #[serde(rename = "R")]
+    Right,
 }
 
-impl FromStr for SecretEntrance {
+/// Turning the dial `distance` clicks in `direction`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rotation {
+    pub direction: Direction,
+    pub distance: u64,
+}
+
+impl Rotation {
+    pub const fn new(direction: Direction, distance: u64) -> Self {
+        Self {
+            direction,
+            distance,
+        }
+    }
+}
+
+impl FromStr for Rotation {
     type Err = anyhow::Error;
 
+    /// Parse rotations like `L68` or `R14`.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut zeros = 0;
-        let mut pass_zeros = 0;
-        let mut sum = 50_i64;
-        for line in s.trim().lines() {
-            let (s, r) = line.split_at(1);
+        let direction = match s.as_bytes().first() {
+            Some(b'L') => Direction::Left,
+            Some(b'R') => Direction::Right,
+            _ => bail!("invalid rotation: {}", s),
+        };
+
+        let distance = s[1..]
+            .parse()
+            .map_err(|_| anyhow!("invalid rotation: {}", s))?;
+
+        Ok(Self::new(direction, distance))
+    }
+}
+
+/// The result of applying a single [`Rotation`] to a [`Dial`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Turn {
+    pub rotation: Rotation,
+    pub from: u64,
+    pub to: u64,
+    /// Whether the dial stopped on the target.
+    pub landed: bool,
+    /// How many clicks pointed at the target, including where it stopped.
+    pub passes: u64,
+}
 
-            let v: i64 = if s == "R" { r.parse()? } else { -r.parse()? };
+/// A dial numbered `0..size` that starts at `start`, where we're interested in
+/// how often it points at `target`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Dial {
+    size: u64,
+    start: u64,
+    target: u64,
+}
+
+impl Default for Dial {
+    /// The dial from the puzzle: 100 positions, starting at 50, watching 0.
+    fn default() -> Self {
+        Self {
+            size: 100,
+            start: 50,
+            target: 0,
+        }
+    }
+}
+
+impl Dial {
+    pub fn new(size: u64, start: u64, target: u64) -> anyhow::Result<Self> {
+        if size == 0 {
+            bail!("a dial needs at least one position");
+        }
+
+        if start >= size || target >= size {
+            bail!(
+                "start ({}) and target ({}) must be less than the size ({})",
+                start,
+                target,
+                size
+            );
+        }
+
+        Ok(Self {
+            size,
+            start,
+            target,
+        })
+    }
+
+    pub const fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub const fn start(&self) -> u64 {
+        self.start
+    }
 
-            pass_zeros += if v < 0 {
-                (100 - sum - v) / 100 - if sum == 0 { 1 } else { 0 }
-            } else {
-                (sum + v) / 100
-            };
+    pub const fn target(&self) -> u64 {
+        self.target
+    }
 
-            sum += v;
-            sum = sum.rem_euclid(100);
+    /// Apply `rotation` with the dial at `position`.
+    ///
+    /// This is constant time regardless of the distance. A `position` past
+    /// the end of the dial wraps around, so `size + 3` is treated as `3`.
+    pub fn turn(&self, position: u64, rotation: Rotation) -> Turn {
+        let position = position % self.size;
 
-            if sum == 0 {
-                zeros += 1;
+        // widened so the sums below can't overflow for sizes near u64::MAX
+        let size = self.size as u128;
+        let (from, target) = (position as u128, self.target as u128);
+        let step = (rotation.distance % self.size) as u128;
+
+        // the number of clicks until the dial first points at the target
+        let (to, first) = match rotation.direction {
+            Direction::Right => ((from + step) % size, (target + size - from) % size),
+            Direction::Left => ((from + size - step) % size, (from + size - target) % size),
+        };
+
+        // both are less than the size, so fit back in a u64
+        let (to, first) = (to as u64, first as u64);
+
+        // if we're already on the target, it takes a full revolution
+        let first = if first == 0 { self.size } else { first };
+
+        let passes = if rotation.distance >= first {
+            (rotation.distance - first) / self.size + 1
+        } else {
+            0
+        };
+
+        Turn {
+            rotation,
+            from: position,
+            to,
+            landed: to == self.target,
+            passes,
+        }
+    }
+
+    /// Apply each of `rotations` in turn, starting from the start position.
+    pub fn turns<I>(&self, rotations: I) -> Turns<'_, I::IntoIter>
+    where
+        I: IntoIterator<Item = Rotation>,
+    {
+        Turns {
+            dial: self,
+            position: self.start,
+            rotations: rotations.into_iter(),
+        }
+    }
+
+    /// How many rotations leave the dial pointing at the target.
+    pub fn count_landings<I>(&self, rotations: I) -> u64
+    where
+        I: IntoIterator<Item = Rotation>,
+    {
+        self.turns(rotations).filter(|t| t.landed).count() as u64
+    }
+
+    /// How many clicks, over all the rotations, point the dial at the target.
+    pub fn count_passes<I>(&self, rotations: I) -> u64
+    where
+        I: IntoIterator<Item = Rotation>,
+    {
+        self.turns(rotations).map(|t| t.passes).sum()
+    }
+}
+
+/// An iterator over the [`Turn`]s for a sequence of rotations, from
+/// [`Dial::turns`].
+#[derive(Debug, Clone)]
+pub struct Turns<'a, I> {
+    dial: &'a Dial,
+    position: u64,
+    rotations: I,
+}
+
+impl<I> Iterator for Turns<'_, I>
+where
+    I: Iterator<Item = Rotation>,
+{
+    type Item = Turn;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let turn = self.dial.turn(self.position, self.rotations.next()?);
+        self.position = turn.to;
+        Some(turn)
+    }
+}
+
+/// Counts how often the dial points at its target, one rotation at a time.
+///
+/// Besides parsing a whole input with `from_str`, rotations can be pushed as
+/// they arrive with [`SecretEntrance::push_rotation`] or read from a
+/// [`BufRead`] with [`SecretEntrance::feed_reader`], which only holds one line
+/// in memory at a time.
+///
+/// ```
+/// use secret_entrance::{Dial, SecretEntrance};
+///
+/// let mut entrance = SecretEntrance::new(Dial::new(10, 0, 5).unwrap());
+/// entrance.feed_reader("R25\nL3\n".as_bytes()).unwrap();
+/// assert_eq!(entrance.position(), 2);
+/// assert_eq!(entrance.zeros(), 1);
+/// assert_eq!(entrance.pass_zeros(), 3);
+///
+/// entrance.push_rotation("R3".parse().unwrap());
+/// assert_eq!(entrance.position(), 5);
+/// assert_eq!(entrance.zeros(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SecretEntrance {
+    dial: Dial,
+    position: u64,
+    zeros: u64,
+    pass_zeros: u64,
+}
+
+impl Default for SecretEntrance {
+    fn default() -> Self {
+        Self::new(Dial::default())
+    }
+}
+
+impl SecretEntrance {
+    pub fn new(dial: Dial) -> Self {
+        Self {
+            dial,
+            position: dial.start(),
+            zeros: 0,
+            pass_zeros: 0,
+        }
+    }
+
+    /// Apply a single rotation, returning what it did.
+    pub fn push_rotation(&mut self, rotation: Rotation) -> Turn {
+        let turn = self.dial.turn(self.position, rotation);
+        self.position = turn.to;
+
+        if turn.landed {
+            self.zeros += 1;
+        }
+        self.pass_zeros += turn.passes;
+
+        aoc_plumbing::trace!(
+            "rotation",
+            direction = turn.rotation.direction,
+            distance = turn.rotation.distance,
+            position = self.position,
+            zeros = self.zeros,
+            passes = self.pass_zeros,
+        );
+
+        turn
+    }
+
+    /// Apply every rotation in `reader`, one per line, returning how many
+    /// there were. Blank lines are skipped.
+    ///
+    /// On error, the rotations before the bad line have still been applied.
+    pub fn feed_reader<R: BufRead>(&mut self, mut reader: R) -> anyhow::Result<usize> {
+        let mut line = String::new();
+        let mut count = 0;
+        let mut line_no = 0;
+
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                return Ok(count);
+            }
+            line_no += 1;
+
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
             }
+
+            let rotation = trimmed
+                .parse()
+                .with_context(|| format!("on line {}", line_no))?;
+            self.push_rotation(rotation);
+            count += 1;
         }
-        Ok(Self { zeros, pass_zeros })
+    }
+
+    pub const fn dial(&self) -> &Dial {
+        &self.dial
+    }
+
+    /// Where the dial is currently pointing.
+    pub const fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// How many rotations so far have left the dial at the target.
+    pub const fn zeros(&self) -> u64 {
+        self.zeros
+    }
+
+    /// How many clicks so far have pointed the dial at the target.
+    pub const fn pass_zeros(&self) -> u64 {
+        self.pass_zeros
+    }
+}
+
+impl FromStr for SecretEntrance {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut entrance = Self::default();
+        for line in s.trim().lines() {
+            entrance.push_rotation(line.parse()?);
+        }
+        Ok(entrance)
     }
 }
 
@@ -43,8 +336,8 @@ impl Problem for SecretEntrance {
     const README: &'static str = include_str!("../README.md");
 
     type ProblemError = anyhow::Error;
-    type P1 = i64;
-    type P2 = i64;
+    type P1 = u64;
+    type P2 = u64;
 
     fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
         Ok(self.zeros)
@@ -61,6 +354,28 @@ mod tests {
 
     use super::*;
 
+    /// Turn the dial one click at a time, to check the closed form against.
+    fn brute(dial: &Dial, rotations: &[Rotation]) -> (u64, u64) {
+        let mut position = dial.start();
+        let mut landings = 0;
+        let mut passes = 0;
+        for r in rotations {
+            for _ in 0..r.distance {
+                position = match r.direction {
+                    Direction::Right => (position + 1) % dial.size(),
+                    Direction::Left => (position + dial.size() - 1) % dial.size(),
+                };
+                if position == dial.target() {
+                    passes += 1;
+                }
+            }
+            if position == dial.target() {
+                landings += 1;
+            }
+        }
+        (landings, passes)
+    }
+
     #[test]
     #[ignore]
     fn full_dataset() {
@@ -84,4 +399,139 @@ L82";
         let solution = SecretEntrance::solve(input).unwrap();
         assert_eq!(solution, Solution::new(3, 6));
     }
+
+    #[test]
+    fn other_dials() {
+        let rotations = "L68 L30 R48 L5 R60 L55 L1 L99 R14 L82 R0 L7 R7 R21 L40"
+            .split(' ')
+            .map(|r| r.parse().unwrap())
+            .collect::<Vec<Rotation>>();
+
+        for size in 1..15 {
+            for start in 0..size {
+                for target in 0..size {
+                    let dial = Dial::new(size, start, target).unwrap();
+                    assert_eq!(
+                        (
+                            dial.count_landings(rotations.iter().copied()),
+                            dial.count_passes(rotations.iter().copied())
+                        ),
+                        brute(&dial, &rotations),
+                        "{:?}",
+                        dial
+                    );
+                }
+            }
+        }
+
+        assert!(Dial::new(0, 0, 0).is_err());
+        assert!(Dial::new(10, 10, 0).is_err());
+        assert!("X12".parse::<Rotation>().is_err());
+        assert!("L".parse::<Rotation>().is_err());
+    }
+
+    #[test]
+    fn huge_dials() {
+        let dial = Dial::new(u64::MAX, u64::MAX - 1, 0).unwrap();
+
+        let turn = dial.turn(u64::MAX - 1, Rotation::new(Direction::Right, 1));
+        assert_eq!((turn.to, turn.landed, turn.passes), (0, true, 1));
+
+        let turn = dial.turn(0, Rotation::new(Direction::Left, 2));
+        assert_eq!(
+            (turn.to, turn.landed, turn.passes),
+            (u64::MAX - 2, false, 0)
+        );
+
+        // a full revolution from the target lands back on it
+        let turn = dial.turn(0, Rotation::new(Direction::Left, u64::MAX));
+        assert_eq!((turn.to, turn.landed, turn.passes), (0, true, 1));
+
+        let turn = dial.turn(u64::MAX - 1, Rotation::new(Direction::Left, u64::MAX));
+        assert_eq!(
+            (turn.to, turn.landed, turn.passes),
+            (u64::MAX - 1, false, 1)
+        );
+    }
+
+    #[test]
+    fn out_of_range_positions() {
+        let dial = Dial::new(100, 50, 0).unwrap();
+        for rotation in [
+            Rotation::new(Direction::Right, 5),
+            Rotation::new(Direction::Left, 5),
+            Rotation::new(Direction::Left, 250),
+        ] {
+            let turn = dial.turn(2, rotation);
+            assert_eq!(dial.turn(102, rotation), turn);
+            assert_eq!(dial.turn(u64::MAX - 13, rotation), turn);
+        }
+
+        let dial = Dial::new(u64::MAX, 0, 0).unwrap();
+        let turn = dial.turn(u64::MAX, Rotation::new(Direction::Left, 1));
+        assert_eq!((turn.from, turn.to, turn.passes), (0, u64::MAX - 1, 0));
+    }
+
+    #[test]
+    fn streaming() {
+        // a big, generated log of rotations
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut rotations = Vec::default();
+        let mut log = String::default();
+        for _ in 0..5_000 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let rotation = Rotation::new(
+                if state & 1 == 0 {
+                    Direction::Left
+                } else {
+                    Direction::Right
+                },
+                (state >> 1) % 750,
+            );
+            log.push_str(&format!(
+                "{}{}\n",
+                if rotation.direction == Direction::Left {
+                    'L'
+                } else {
+                    'R'
+                },
+                rotation.distance
+            ));
+            rotations.push(rotation);
+        }
+
+        for dial in [Dial::default(), Dial::new(7, 3, 6).unwrap()] {
+            let mut entrance = SecretEntrance::new(dial);
+            // a tiny buffer so lines arrive in pieces
+            let reader = std::io::BufReader::with_capacity(3, log.as_bytes());
+            assert_eq!(entrance.feed_reader(reader).unwrap(), rotations.len());
+
+            assert_eq!(
+                (entrance.zeros(), entrance.pass_zeros()),
+                brute(&dial, &rotations)
+            );
+            assert_eq!(
+                entrance.position(),
+                dial.turns(rotations.iter().copied()).last().unwrap().to
+            );
+        }
+
+        let streamed = {
+            let mut entrance = SecretEntrance::default();
+            entrance.feed_reader(log.as_bytes()).unwrap();
+            (entrance.zeros(), entrance.pass_zeros())
+        };
+        let mut parsed = SecretEntrance::from_str(&log).unwrap();
+        assert_eq!(
+            streamed,
+            (parsed.part_one().unwrap(), parsed.part_two().unwrap())
+        );
+
+        let mut entrance = SecretEntrance::default();
+        let err = entrance.feed_reader("R10\n\nL5\nX3\nR1\n".as_bytes());
+        assert_eq!(err.unwrap_err().to_string(), "on line 4");
+        assert_eq!(entrance.position(), 55);
+    }
 }
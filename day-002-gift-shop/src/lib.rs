@@ -2,158 +2,391 @@ use std::str::FromStr;
 
 use anyhow::anyhow;
 use aoc_plumbing::Problem;
-use rustc_hash::FxHashSet;
-
-const ONE_PATTERNS: [usize; 9] = [
-    11, 111, 1111, 11111, 111111, 1111111, 11111111, 111111111, 1111111111,
-];
-const TWO_PATTERNS: [usize; 4] = [
-    101,       // 4
-    10101,     // 6
-    1010101,   // 8
-    101010101, // 10
-];
-const THREE_PATTERNS: [usize; 2] = [
-    1001,    // 6
-    1001001, // 9
-];
-const FOUR_PATTERNS: [usize; 1] = [
-    10001, // 8
-];
-const FIVE_PATTERNS: [usize; 1] = [
-    100001, // 10
-];
-
-const PATTERNS: [&[usize]; 5] = [
-    &ONE_PATTERNS,
-    &TWO_PATTERNS,
-    &THREE_PATTERNS,
-    &FOUR_PATTERNS,
-    &FIVE_PATTERNS,
-];
-
-#[derive(Debug, Clone, Copy)]
-struct IdRange {
-    left: usize,
-    right: usize,
+
+/// Which ids made of a repeated block of digits count as invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Repetition {
+    /// Some block repeated exactly this many times, made with
+    /// [`Repetition::exactly`]. Twice is ids like `123123`, as well as `1111`
+    /// (`11` twice).
+    Exactly(Reps),
+    /// Some block repeated two or more times, like `123123` or `121212`.
+    AtLeastTwice,
 }
 
-impl IdRange {
-    fn invalid_sum(&self) -> usize {
-        let mut sum = 0;
+impl Repetition {
+    /// Some block repeated exactly `reps` times, or `None` if `reps` is less
+    /// than 2, since every id is a block repeated once.
+    ///
+    /// ```
+    /// use gift_shop::Repetition;
+    ///
+    /// assert!(Repetition::exactly(2).is_some());
+    /// assert_eq!(Repetition::exactly(1), None);
+    /// ```
+    pub const fn exactly(reps: u32) -> Option<Self> {
+        if reps < 2 {
+            None
+        } else {
+            Some(Self::Exactly(Reps(reps)))
+        }
+    }
+}
 
-        let mut left = self.left;
-        let mut right = self.right;
+/// How many times a block repeats, which is always at least 2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Reps(u32);
 
-        let digits_left = left.checked_ilog10().unwrap_or(0) + 1;
-        let mut digits_right = right.checked_ilog10().unwrap_or(0) + 1;
+impl Reps {
+    /// The number of repetitions.
+    pub const fn get(self) -> u32 {
+        self.0
+    }
+}
 
-        if !digits_right.is_multiple_of(2) {
-            right = 10_usize.pow(digits_right - 1) - 1;
-            digits_right -= 1;
-        }
+/// The number that repeats a `block_len` digit block `reps` times when
+/// multiplied by it, like `1001` for `(3, 2)` or `10101` for `(2, 3)`.
+///
+/// Returns `None` if that doesn't fit in a `u128`.
+///
+/// ```
+/// use gift_shop::repetition_multiplier;
+///
+/// assert_eq!(repetition_multiplier(3, 2), Some(1001));
+/// assert_eq!(repetition_multiplier(2, 3), Some(10101));
+/// assert_eq!(123 * repetition_multiplier(3, 2).unwrap(), 123123);
+/// assert_eq!(repetition_multiplier(20, 3), None);
+/// ```
+pub fn repetition_multiplier(block_len: u32, reps: u32) -> Option<u128> {
+    let shift = 10_u128.checked_pow(block_len)?;
+    let mut mult = 0_u128;
+    for _ in 0..reps {
+        mult = mult.checked_mul(shift)?.checked_add(1)?;
+    }
+    Some(mult)
+}
 
-        if digits_left < digits_right {
-            left = 10_usize.pow(digits_right - 1);
-        }
+fn digits(n: u128) -> u32 {
+    n.checked_ilog10().unwrap_or(0) + 1
+}
 
-        let half_shift = 10_usize.pow(digits_right / 2);
-        let half = left / half_shift;
-        let mut cur = half;
+/// An inclusive range of ids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IdRange {
+    pub left: u128,
+    pub right: u128,
+}
 
-        loop {
-            let candidate = cur * half_shift + cur;
-            if candidate > right {
-                return sum;
-            }
-            if candidate >= left {
-                sum += candidate;
-            }
+impl IdRange {
+    pub const fn new(left: u128, right: u128) -> Self {
+        Self { left, right }
+    }
 
-            cur += 1;
-        }
+    /// The sum of the ids in this range that are some block repeated twice,
+    /// or `None` if that doesn't fit in a `u128`.
+    pub fn invalid_sum(&self) -> Option<u128> {
+        self.invalid_sum_with(Repetition::Exactly(Reps(2)))
     }
 
-    fn invalid_sum_cached(&self, cache: &mut FxHashSet<usize>) -> usize {
-        let mut sum = 0;
+    /// The sum of the ids in this range that are some block repeated at least
+    /// twice, or `None` if that doesn't fit in a `u128`.
+    pub fn multiple_invalid_sum(&self) -> Option<u128> {
+        self.invalid_sum_with(Repetition::AtLeastTwice)
+    }
 
-        let mut left = self.left;
-        let mut right = self.right;
+    /// The sum of the ids in this range that are invalid under `mode`, or
+    /// `None` if that doesn't fit in a `u128`, which only happens for ranges
+    /// of huge ids.
+    ///
+    /// ```
+    /// use gift_shop::{IdRange, Repetition};
+    ///
+    /// let range = IdRange::new(100_000, 999_999);
+    /// // 111111 is both 111 twice and 11 three times, but only counts once
+    /// assert_eq!(range.invalid_sum_with(Repetition::exactly(2).unwrap()), Some(900 * (100_100 + 999_999) / 2));
+    /// assert_eq!(range.invalid_sum_with(Repetition::exactly(3).unwrap()), Some(90 * (101_010 + 999_999) / 2));
+    /// assert_eq!(range.invalid_sum_with(Repetition::exactly(6).unwrap()), Some(9 * (111_111 + 999_999) / 2));
+    /// assert_eq!(
+    ///     range.invalid_sum_with(Repetition::AtLeastTwice),
+    ///     Some((900 * (100_100 + 999_999) + 90 * (101_010 + 999_999)) / 2 - 9 * (111_111 + 999_999) / 2),
+    /// );
+    ///
+    /// // every 39 digit id made of 13 repeated 3 digit blocks
+    /// let range = IdRange::new(10_u128.pow(38), u128::MAX);
+    /// assert_eq!(range.invalid_sum_with(Repetition::exactly(13).unwrap()), None);
+    /// ```
+    pub fn invalid_sum_with(&self, mode: Repetition) -> Option<u128> {
+        self.tally(mode, |mult, lo, hi| {
+            // an arithmetic series, halving whichever factor is even
+            let count = hi - lo + 1;
+            let ends = lo.checked_add(hi)?;
+            let series = if count.is_multiple_of(2) {
+                (count / 2).checked_mul(ends)?
+            } else {
+                (ends / 2).checked_mul(count)?
+            };
+
+            mult.checked_mul(series)
+        })
+    }
 
-        let digits_left = left.checked_ilog10().unwrap_or(0) + 1;
-        let mut digits_right = right.checked_ilog10().unwrap_or(0) + 1;
+    /// How many ids in this range are invalid under `mode`.
+    ///
+    /// ```
+    /// use gift_shop::{IdRange, Repetition};
+    ///
+    /// let range = IdRange::new(1, 9999);
+    /// // 11..99, then 1010..9999 as well as 1111 and friends
+    /// assert_eq!(range.invalid_count(Repetition::exactly(2).unwrap()), 9 + 90);
+    /// assert_eq!(range.invalid_count(Repetition::exactly(3).unwrap()), 9);
+    /// assert_eq!(range.invalid_count(Repetition::AtLeastTwice), 9 + 9 + 90);
+    /// ```
+    pub fn invalid_count(&self, mode: Repetition) -> u128 {
+        // there are only around 10^20 ids that repeat a block at all
+        self.tally(mode, |_, lo, hi| Some(hi - lo + 1))
+            .expect("the count of invalid ids fits in a u128")
+    }
 
-        if !digits_right.is_multiple_of(2) {
-            right = 10_usize.pow(digits_right - 1) - 1;
-            digits_right -= 1;
-        }
+    /// Every id in this range that's invalid under `mode`, in increasing
+    /// order, along with the shortest block it repeats.
+    ///
+    /// The ids are produced lazily, so this is fine to use on huge ranges.
+    ///
+    /// ```
+    /// use gift_shop::{IdRange, InvalidId, Repetition};
+    ///
+    /// let range = IdRange::new(1000, 1300);
+    /// let ids: Vec<_> = range.invalid_ids(Repetition::AtLeastTwice).collect();
+    /// assert_eq!(
+    ///     ids,
+    ///     [
+    ///         InvalidId { id: 1010, period: 2 },
+    ///         InvalidId { id: 1111, period: 1 },
+    ///         InvalidId { id: 1212, period: 2 },
+    ///     ]
+    /// );
+    /// assert_eq!(ids[1].reps(), 4);
+    /// ```
+    pub fn invalid_ids(&self, mode: Repetition) -> InvalidIds {
+        let (len, max_len) = if self.left > self.right {
+            (1, 0)
+        } else {
+            (digits(self.left), digits(self.right))
+        };
 
-        if digits_left < digits_right {
-            left = 10_usize.pow(digits_right - 1);
+        InvalidIds {
+            range: *self,
+            mode,
+            len,
+            max_len,
+            streams: Vec::default(),
         }
+    }
 
-        let half_shift = 10_usize.pow(digits_right / 2);
-        let half = left / half_shift;
-        let mut cur = half;
+    /// Combine `per_blocks(multiplier, smallest block, largest block)` over
+    /// the ids that are invalid under `mode`, where the result for a set of
+    /// ids is the sum of the results for its parts.
+    ///
+    /// Returns `None` if `per_blocks` does, or if any of the sums overflow.
+    fn tally(
+        &self,
+        mode: Repetition,
+        per_blocks: impl Fn(u128, u128, u128) -> Option<u128>,
+    ) -> Option<u128> {
+        if self.left > self.right {
+            return Some(0);
+        }
 
-        loop {
-            let candidate = cur * half_shift + cur;
-            if candidate > right {
-                return sum;
-            }
-            if candidate >= left {
-                cache.insert(candidate);
-                sum += candidate;
-            }
+        let repeated = |block_len, reps| {
+            self.blocks(block_len, reps)
+                .map_or(Some(0), |(mult, lo, hi)| per_blocks(mult, lo, hi))
+        };
 
-            cur += 1;
+        let mut lengths = digits(self.left)..=digits(self.right);
+        match mode {
+            Repetition::Exactly(Reps(reps)) => lengths
+                .filter(|len| len.is_multiple_of(reps))
+                .try_fold(0_u128, |total, len| {
+                    total.checked_add(repeated(len / reps, reps)?)
+                }),
+            Repetition::AtLeastTwice => lengths.try_fold(0_u128, |total, len| {
+                // ids repeating a block of length `d` include all of the
+                // ones repeating a block whose length divides `d` (111111
+                // is 111 twice, 11 three times and 1 six times), so weight
+                // by the möbius function to count every id exactly once
+                let mut add = 0_u128;
+                let mut sub = 0_u128;
+                for block_len in (1..len).filter(|b| len.is_multiple_of(*b)) {
+                    match mobius(len / block_len) {
+                        -1 => add = add.checked_add(repeated(block_len, len / block_len)?)?,
+                        1 => sub = sub.checked_add(repeated(block_len, len / block_len)?)?,
+                        _ => {}
+                    }
+                }
+                total.checked_add(add - sub)
+            }),
         }
     }
 
-    fn multiple_invalid_sum(&self) -> usize {
-        let mut seen = FxHashSet::default();
-        let mut sum = self.invalid_sum_cached(&mut seen);
+    /// The multiplier along with the smallest and largest blocks for the ids
+    /// in this range that are a `block_len` digit block repeated `reps` times,
+    /// if there are any.
+    fn blocks(&self, block_len: u32, reps: u32) -> Option<(u128, u128, u128)> {
+        let mult = repetition_multiplier(block_len, reps)?;
+        let smallest = 10_u128.checked_pow(block_len.checked_sub(1)?)?;
+        let largest = 10_u128.checked_pow(block_len).map_or(u128::MAX, |v| v - 1);
+
+        let lo = smallest.max(self.left.div_ceil(mult));
+        let hi = largest.min(self.right / mult);
+        (lo <= hi).then_some((mult, lo, hi))
+    }
+}
 
-        let digits_left = self.left.checked_ilog10().unwrap_or(0) + 1;
-        let digits_right = self.right.checked_ilog10().unwrap_or(0) + 1;
+/// An invalid id, and the length of the shortest block it's a repetition of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InvalidId {
+    pub id: u128,
+    pub period: u32,
+}
 
-        let mut digits_cur = 1;
-        let mut cur = if digits_left < digits_right {
-            // we can start cur at any
-            1
-        } else {
-            // we have to start cur at the first digit of left
-            let mut left = self.left;
-            loop {
-                if left < 10 {
-                    break left;
-                }
+impl InvalidId {
+    /// How many times the shortest block repeats.
+    pub fn reps(&self) -> u32 {
+        digits(self.id) / self.period
+    }
+}
 
-                left /= 10;
-            }
+/// The invalid ids in a range, from [`IdRange::invalid_ids`].
+#[derive(Debug, Clone)]
+pub struct InvalidIds {
+    range: IdRange,
+    mode: Repetition,
+    /// The number of digits of the ids currently in `streams`.
+    len: u32,
+    max_len: u32,
+    streams: Vec<Blocks>,
+}
+
+impl InvalidIds {
+    /// Set up the streams for the ids with `len` digits.
+    fn start_len(&mut self, len: u32) {
+        let range = self.range;
+        let stream = |block_len, reps, primitive| {
+            range
+                .blocks(block_len, reps)
+                .map(|(mult, next, hi)| Blocks {
+                    mult,
+                    block_len,
+                    next: Some(next),
+                    hi,
+                    primitive,
+                })
         };
 
+        self.streams.clear();
+        match self.mode {
+            Repetition::Exactly(Reps(reps)) => {
+                if len.is_multiple_of(reps) {
+                    self.streams.extend(stream(len / reps, reps, false));
+                }
+            }
+            Repetition::AtLeastTwice => {
+                // only taking the blocks that aren't repetitions themselves
+                // means every id shows up in exactly one stream
+                for block_len in (1..len).filter(|b| len.is_multiple_of(*b)) {
+                    self.streams
+                        .extend(stream(block_len, len / block_len, true));
+                }
+            }
+        }
+    }
+}
+
+impl Iterator for InvalidIds {
+    type Item = InvalidId;
+
+    fn next(&mut self) -> Option<Self::Item> {
         loop {
-            for pat in PATTERNS[digits_cur as usize - 1] {
-                let candidate = pat * cur;
+            // the streams are disjoint, so merging them is just taking the
+            // smallest each time
+            let next = self
+                .streams
+                .iter_mut()
+                .filter_map(|s| s.peek().map(|block| (block * s.mult, block, s)))
+                .min_by_key(|(id, _, _)| *id);
+
+            if let Some((id, block, stream)) = next {
+                stream.next = block.checked_add(1);
+                return Some(InvalidId {
+                    id,
+                    period: period(block, stream.block_len),
+                });
+            }
 
-                if candidate > self.right {
-                    break;
-                }
+            if self.len > self.max_len {
+                return None;
+            }
+            self.start_len(self.len);
+            self.len += 1;
+        }
+    }
+}
 
-                if candidate >= self.left && seen.insert(candidate) {
-                    sum += candidate;
-                }
+/// The ids that are consecutive blocks multiplied by `mult`.
+#[derive(Debug, Clone)]
+struct Blocks {
+    mult: u128,
+    block_len: u32,
+    /// The next block to try, or `None` once past the largest `u128`.
+    next: Option<u128>,
+    hi: u128,
+    /// Skip blocks that are repetitions of a shorter block.
+    primitive: bool,
+}
+
+impl Blocks {
+    /// The next block, without moving past it.
+    fn peek(&mut self) -> Option<u128> {
+        while let Some(block) = self.next.filter(|b| *b <= self.hi) {
+            if !self.primitive || period(block, self.block_len) == self.block_len {
+                return Some(block);
             }
+            self.next = block.checked_add(1);
+        }
+        None
+    }
+}
 
-            cur += 1;
-            digits_cur = cur.checked_ilog10().unwrap_or(0) + 1;
+/// The length of the shortest block that `block` is a repetition of, given
+/// that it has `block_len` digits.
+fn period(block: u128, block_len: u32) -> u32 {
+    (1..block_len)
+        .filter(|d| block_len.is_multiple_of(*d))
+        .find(|d| {
+            let head = block / 10_u128.pow(block_len - d);
+            repetition_multiplier(*d, block_len / d)
+                .is_some_and(|mult| head.checked_mul(mult) == Some(block))
+        })
+        .unwrap_or(block_len)
+}
 
-            if digits_cur > digits_right / 3 {
-                return sum;
+/// The möbius function, which is 0 if `n` has a squared prime factor, and
+/// otherwise 1 or -1 for an even or odd number of prime factors.
+fn mobius(mut n: u32) -> i8 {
+    let mut res = 1;
+    let mut p = 2;
+    while p * p <= n {
+        if n.is_multiple_of(p) {
+            n /= p;
+            if n.is_multiple_of(p) {
+                return 0;
             }
+            res = -res;
         }
+        p += 1;
     }
+
+    if n > 1 { -res } else { res }
 }
 
 impl FromStr for IdRange {
@@ -164,10 +397,7 @@ impl FromStr for IdRange {
             .split_once('-')
             .ok_or_else(|| anyhow!("invalid range: {}", s))?;
 
-        let left: usize = left_c.parse()?;
-        let right: usize = right_c.parse()?;
-
-        Ok(IdRange { left, right })
+        Ok(IdRange::new(left_c.parse()?, right_c.parse()?))
     }
 }
 
@@ -176,6 +406,41 @@ pub struct GiftShop {
     ranges: Vec<IdRange>,
 }
 
+impl GiftShop {
+    pub fn ranges(&self) -> &[IdRange] {
+        &self.ranges
+    }
+
+    /// The sum, over every range, of the ids that are invalid under `mode`,
+    /// or `None` if that doesn't fit in a `u128`.
+    pub fn invalid_sum(&self, mode: Repetition) -> Option<u128> {
+        self.ranges.iter().try_fold(0_u128, |total, r| {
+            total.checked_add(r.invalid_sum_with(mode)?)
+        })
+    }
+
+    /// The sum of every range's invalid ids, tracing each range along the way.
+    fn checked_total(&self, part: u8, mode: Repetition) -> anyhow::Result<u128> {
+        self.ranges.iter().try_fold(0_u128, |total, r| {
+            let sum = r.invalid_sum_with(mode);
+            aoc_plumbing::trace!(
+                "range",
+                part = part,
+                left = r.left,
+                right = r.right,
+                sum = sum
+            );
+            sum.and_then(|sum| total.checked_add(sum)).ok_or_else(|| {
+                anyhow!(
+                    "the invalid ids up to {}-{} overflow a u128",
+                    r.left,
+                    r.right
+                )
+            })
+        })
+    }
+}
+
 impl FromStr for GiftShop {
     type Err = anyhow::Error;
 
@@ -195,15 +460,15 @@ impl Problem for GiftShop {
     const README: &'static str = include_str!("../README.md");
 
     type ProblemError = anyhow::Error;
-    type P1 = usize;
-    type P2 = usize;
+    type P1 = u128;
+    type P2 = u128;
 
     fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
-        Ok(self.ranges.iter().map(|r| r.invalid_sum()).sum())
+        self.checked_total(1, Repetition::Exactly(Reps(2)))
     }
 
     fn part_two(&mut self) -> Result<Self::P2, Self::ProblemError> {
-        Ok(self.ranges.iter().map(|r| r.multiple_invalid_sum()).sum())
+        self.checked_total(2, Repetition::AtLeastTwice)
     }
 }
 
@@ -213,6 +478,63 @@ mod tests {
 
     use super::*;
 
+    /// Ranges of up to 20 digits, narrow enough to brute force, half of them
+    /// around an id that repeats a block.
+    fn random_ranges() -> Vec<IdRange> {
+        let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u128
+        };
+
+        (0..2_000)
+            .map(|i| {
+                let left = if i % 2 == 0 {
+                    next() % 10_u128.pow((next() % 20) as u32 + 1)
+                } else {
+                    let reps = (next() % 4) as usize + 2;
+                    let block_len = (next() % (20 / reps as u128)) as u32 + 1;
+                    let smallest = 10_u128.pow(block_len - 1);
+                    let block = smallest + next() % (9 * smallest);
+                    let id: u128 = block.to_string().repeat(reps).parse().unwrap();
+                    id.saturating_sub(next() % 10_u128.pow((next() % 4) as u32))
+                };
+                let right = left + next() % 10_u128.pow((next() % 4) as u32);
+                IdRange::new(left, right)
+            })
+            .collect()
+    }
+
+    const MODES: [Repetition; 5] = [
+        Repetition::AtLeastTwice,
+        Repetition::exactly(2).unwrap(),
+        Repetition::exactly(3).unwrap(),
+        Repetition::exactly(5).unwrap(),
+        Repetition::exactly(6).unwrap(),
+    ];
+
+    /// Whether `id` is some block of digits repeated `reps` times.
+    fn repeats(id: &[u8], reps: usize) -> bool {
+        id.len().is_multiple_of(reps) && id.chunks(id.len() / reps).all(|c| c == &id[..c.len()])
+    }
+
+    /// The sum from checking every id in the range one at a time, to check
+    /// the closed form against.
+    fn brute_force_sum(range: &IdRange, mode: Repetition) -> u128 {
+        (range.left..=range.right)
+            .filter(|id| {
+                let id = id.to_string();
+                let id = id.as_bytes();
+                match mode {
+                    Repetition::Exactly(reps) => repeats(id, reps.get() as usize),
+                    Repetition::AtLeastTwice => (2..=id.len()).any(|reps| repeats(id, reps)),
+                }
+            })
+            .sum()
+    }
+
     #[test]
     #[ignore]
     fn full_dataset() {
@@ -228,4 +550,153 @@ mod tests {
         let solution = GiftShop::solve(input).unwrap();
         assert_eq!(solution, Solution::new(1227775554, 4174379265));
     }
+
+    #[test]
+    fn wide_ranges() {
+        // past the 10 digits and `usize` the old tables were limited to
+        let id = 98_765_432_101_234_567_890_u128;
+        let range = IdRange::new(id - 5, id + 5);
+        assert_eq!(
+            range.invalid_sum_with(Repetition::exactly(2).unwrap()),
+            Some(0)
+        );
+        let id = 1_234_567_890_u128 * repetition_multiplier(10, 2).unwrap();
+        let range = IdRange::new(id - 5, id + 5);
+        assert_eq!(
+            range.invalid_sum_with(Repetition::exactly(2).unwrap()),
+            Some(id)
+        );
+        assert_eq!(range.multiple_invalid_sum(), Some(id));
+
+        // every 20 digit id that's a 10 digit block twice
+        let range = IdRange::new(10_u128.pow(19), 10_u128.pow(20) - 1);
+        let blocks = 10_u128.pow(10) - 10_u128.pow(9);
+        assert_eq!(
+            range.invalid_sum(),
+            Some(
+                repetition_multiplier(10, 2).unwrap()
+                    * blocks
+                    * (10_u128.pow(9) + 10_u128.pow(10) - 1)
+                    / 2
+            )
+        );
+
+        // 39 digits, the most a u128 has room for
+        let id = 250 * repetition_multiplier(3, 13).unwrap();
+        let range = IdRange::new(id - 1, id + 1);
+        assert_eq!(
+            range.invalid_sum_with(Repetition::exactly(13).unwrap()),
+            Some(id)
+        );
+        assert_eq!(range.multiple_invalid_sum(), Some(id));
+        assert_eq!(
+            range.invalid_sum_with(Repetition::exactly(39).unwrap()),
+            Some(0)
+        );
+
+        assert_eq!(IdRange::new(1, 9).multiple_invalid_sum(), Some(0));
+        assert_eq!(IdRange::new(9, 1).multiple_invalid_sum(), Some(0));
+        assert_eq!(Repetition::exactly(1), None);
+        assert_eq!(Repetition::exactly(0), None);
+    }
+
+    #[test]
+    fn overflow() {
+        // the sum of every 39 digit id made of repeated blocks is far past
+        // u128::MAX, even though each id fits
+        let range = IdRange::new(10_u128.pow(38), u128::MAX);
+        assert_eq!(
+            range.invalid_sum_with(Repetition::exactly(3).unwrap()),
+            None
+        );
+        assert_eq!(range.multiple_invalid_sum(), None);
+        assert!(range.invalid_count(Repetition::AtLeastTwice) > 0);
+
+        let id = 250 * repetition_multiplier(3, 13).unwrap();
+        let input = format!("{}-{},{}-{}", id, id, id, id);
+        assert!(GiftShop::solve(&input).is_err());
+        let shop: GiftShop = input.parse().unwrap();
+        assert_eq!(shop.invalid_sum(Repetition::exactly(13).unwrap()), None);
+        assert_eq!(shop.invalid_sum(Repetition::exactly(39).unwrap()), Some(0),);
+
+        // the last block ends the stream rather than overflowing the cursor
+        let ids = InvalidIds {
+            range: IdRange::new(u128::MAX - 1, u128::MAX),
+            mode: Repetition::AtLeastTwice,
+            len: 40,
+            max_len: 39,
+            streams: vec![Blocks {
+                mult: 1,
+                block_len: 39,
+                next: Some(u128::MAX - 1),
+                hi: u128::MAX,
+                primitive: false,
+            }],
+        };
+        assert_eq!(
+            ids.map(|i| i.id).collect::<Vec<_>>(),
+            [u128::MAX - 1, u128::MAX]
+        );
+    }
+
+    #[test]
+    fn closed_form() {
+        assert_eq!(
+            (1..=12).map(mobius).collect::<Vec<_>>(),
+            [1, -1, -1, 0, -1, 1, -1, 0, 0, 1, -1, 0]
+        );
+
+        for range in random_ranges() {
+            for mode in MODES {
+                assert_eq!(
+                    range.invalid_sum_with(mode),
+                    Some(brute_force_sum(&range, mode)),
+                    "{:?} {:?}",
+                    range,
+                    mode
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn enumeration() {
+        for range in random_ranges() {
+            for mode in MODES {
+                let ids = range.invalid_ids(mode).collect::<Vec<_>>();
+                assert!(ids.windows(2).all(|w| w[0].id < w[1].id));
+                assert_eq!(ids.len() as u128, range.invalid_count(mode));
+                assert_eq!(
+                    ids.iter().map(|i| i.id).sum::<u128>(),
+                    brute_force_sum(&range, mode)
+                );
+
+                for id in ids {
+                    assert!(range.left <= id.id && id.id <= range.right);
+                    let block = id.id / 10_u128.pow(digits(id.id) - id.period);
+                    assert_eq!(
+                        block * repetition_multiplier(id.period, id.reps()).unwrap(),
+                        id.id
+                    );
+                    // nothing shorter repeats to make the id
+                    assert_eq!(period(block, id.period), id.period);
+                }
+            }
+        }
+
+        // lazy enough to take a few from a huge range
+        let ids = IdRange::new(0, u128::MAX)
+            .invalid_ids(Repetition::AtLeastTwice)
+            .map(|i| i.id)
+            .skip(8)
+            .take(3)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [99, 111, 222]);
+        assert_eq!(
+            IdRange::new(9, 1)
+                .invalid_ids(Repetition::AtLeastTwice)
+                .count(),
+            0
+        );
+    }
 }
@@ -1,95 +1,184 @@
-use std::str::FromStr;
+use std::{fmt::Display, str::FromStr};
 
+use anyhow::{anyhow, bail};
 use aoc_plumbing::Problem;
 
-#[derive(Debug, Clone)]
-pub struct Lobby {
-    p1: usize,
-    p2: usize,
+pub mod scan;
+
+/// The batteries chosen from a bank, in the order they appear in it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Joltage {
+    digits: String,
+    indices: Vec<usize>,
 }
 
-impl FromStr for Lobby {
-    type Err = anyhow::Error;
+impl Joltage {
+    /// The joltage as a number, if it fits in a `u128` (which is always the
+    /// case for up to 38 batteries).
+    pub fn value(&self) -> Option<u128> {
+        if self.digits.is_empty() {
+            return Some(0);
+        }
+        self.digits.parse().ok()
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut p1 = 0;
-        let mut p2 = 0;
+    /// The joltage as a string of digits, for any number of batteries.
+    pub fn as_str(&self) -> &str {
+        &self.digits
+    }
 
-        for l in s.trim().lines() {
-            let battery = l.as_bytes();
-            let mut max_pos = 0;
-            let mut max_v = 0;
-
-            #[allow(clippy::needless_range_loop)]
-            for i in 0..(battery.len() - 12) {
-                let v = battery[i] - b'0';
-                if v > max_v {
-                    max_v = v;
-                    max_pos = i;
-
-                    if v == 9 {
-                        break;
-                    }
-                }
-            }
+    /// Where each chosen battery is in the bank.
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+}
 
-            let shrunk = &battery[max_pos..];
+impl Display for Joltage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.digits)
+    }
+}
+
+/// The largest joltage from turning on exactly `k` of the batteries in `bank`.
+///
+/// This keeps a monotonic stack of chosen digits, dropping smaller digits
+/// whenever a larger one comes along for as long as there are enough
+/// batteries left to still pick `k`, so it's O(n) for any `k`.
+///
+/// ```
+/// use lobby::best_joltage;
+///
+/// let joltage = best_joltage("818181911112111", 2).unwrap();
+/// assert_eq!(joltage.value(), Some(92));
+/// assert_eq!(joltage.indices(), [6, 11]);
+///
+/// let joltage = best_joltage("818181911112111", 12).unwrap();
+/// assert_eq!(joltage.as_str(), "888911112111");
+///
+/// assert!(best_joltage("8181", 12).is_err());
+/// ```
+pub fn best_joltage(bank: &str, k: usize) -> anyhow::Result<Joltage> {
+    let bytes = bank.as_bytes();
+    if bytes.len() < k {
+        bail!(
+            "a bank of {} batteries is too short to turn on {}",
+            bytes.len(),
+            k
+        );
+    }
+
+    let mut droppable = bytes.len() - k;
+    let mut chosen: Vec<(usize, u8)> = Vec::with_capacity(bytes.len());
 
-            p1 += best_battery2(shrunk);
-            p2 += best_battery12(shrunk);
+    for (idx, b) in bytes.iter().enumerate() {
+        if !b.is_ascii_digit() {
+            bail!("invalid battery {:?} in bank: {}", *b as char, bank);
         }
 
-        Ok(Self { p1, p2 })
+        // strictly smaller, so ties keep the earlier battery
+        while droppable > 0 && chosen.last().is_some_and(|(_, top)| top < b) {
+            chosen.pop();
+            droppable -= 1;
+        }
+        chosen.push((idx, *b));
     }
+
+    chosen.truncate(k);
+
+    Ok(Joltage {
+        digits: chosen.iter().map(|(_, b)| *b as char).collect(),
+        indices: chosen.iter().map(|(idx, _)| *idx).collect(),
+    })
 }
 
-fn best_battery2(choices: &[u8]) -> usize {
-    let mut first = choices[0] - b'0';
-    let mut max_pos = 0;
-    if first != 9 {
-        #[allow(clippy::needless_range_loop)]
-        for i in 0..(choices.len() - 1) {
-            let v = choices[i] - b'0';
-            if v > first {
-                first = v;
-                max_pos = i;
-
-                if v == 9 {
-                    break;
-                }
-            }
-        }
+/// The same as [`best_joltage`], but picking each battery in turn as the
+/// first largest digit that still leaves enough batteries after it, using
+/// `max_digit` to find it (see [`scan`]).
+///
+/// This is O(n * k) in the worst case, but finding the digits is fast enough
+/// that it's quicker for small `k`.
+///
+/// ```
+/// use lobby::{best_joltage, best_joltage_by, scan};
+///
+/// let bank = "234234234234278";
+/// assert_eq!(
+///     best_joltage_by(bank, 12, scan::max_digit_swar).unwrap(),
+///     best_joltage(bank, 12).unwrap(),
+/// );
+/// ```
+pub fn best_joltage_by<F>(bank: &str, k: usize, max_digit: F) -> anyhow::Result<Joltage>
+where
+    F: Fn(&[u8]) -> Option<(usize, u8)>,
+{
+    let bytes = bank.as_bytes();
+    if bytes.len() < k {
+        bail!(
+            "a bank of {} batteries is too short to turn on {}",
+            bytes.len(),
+            k
+        );
     }
 
-    let mut second = 0;
-    for ch in choices[max_pos + 1..].iter() {
-        let v = ch - b'0';
-        if v > second {
-            second = v;
-            if v == 9 {
-                break;
-            }
-        }
+    if let Some(b) = bytes.iter().find(|b| !b.is_ascii_digit()) {
+        bail!("invalid battery {:?} in bank: {}", *b as char, bank);
     }
 
-    (first * 10 + second) as usize
+    let mut digits = String::with_capacity(k);
+    let mut indices = Vec::with_capacity(k);
+    let mut start = 0;
+    for remaining in (0..k).rev() {
+        let (offset, d) = max_digit(&bytes[start..(bytes.len() - remaining)])
+            .ok_or_else(|| anyhow!("no batteries left to choose from"))?;
+        digits.push(d as char);
+        indices.push(start + offset);
+        start += offset + 1;
+    }
+
+    Ok(Joltage { digits, indices })
 }
 
-fn best_battery12(choices: &[u8]) -> usize {
-    let mut max = 0;
-    let mut start = 0;
-    for pos in 0..12 {
-        let exclude = 11 - pos as usize;
-        let (max_idx, max_digit) = choices[start..(choices.len() - exclude)]
-            .iter()
-            .enumerate()
-            .rev()
-            .max_by_key(|(_, d)| *d)
-            .expect("we know this has at least one element");
-        start += max_idx + 1;
-        max += (max_digit - b'0') as usize * 10_usize.pow(11 - pos);
+/// The strategy used by the solver: the O(n) [`best_joltage`], unless the
+/// `swar` feature opts in to [`best_joltage_by`] with the SWAR scanner.
+fn solver_joltage(bank: &str, k: usize) -> anyhow::Result<Joltage> {
+    #[cfg(feature = "swar")]
+    let joltage = |bank: &str, k| best_joltage_by(bank, k, scan::max_digit_swar);
+    #[cfg(not(feature = "swar"))]
+    let joltage = best_joltage;
+
+    joltage(bank, k)
+}
+
+#[derive(Debug, Clone)]
+pub struct Lobby {
+    p1: u128,
+    p2: u128,
+}
+
+impl FromStr for Lobby {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut p1 = 0;
+        let mut p2 = 0;
+
+        for (bank, l) in s.trim().lines().enumerate() {
+            let two = solver_joltage(l, 2)?;
+            let twelve = solver_joltage(l, 12)?;
+            aoc_plumbing::trace!(
+                "bank",
+                bank = bank,
+                two = two.as_str(),
+                twelve = twelve.as_str(),
+                chosen = twelve.indices(),
+            );
+
+            p1 += two.value().ok_or_else(|| anyhow!("joltage too large"))?;
+            p2 += twelve.value().ok_or_else(|| anyhow!("joltage too large"))?;
+        }
+
+        Ok(Self { p1, p2 })
     }
-    max
 }
 
 impl Problem for Lobby {
@@ -98,8 +187,8 @@ impl Problem for Lobby {
     const README: &'static str = include_str!("../README.md");
 
     type ProblemError = anyhow::Error;
-    type P1 = usize;
-    type P2 = usize;
+    type P1 = u128;
+    type P2 = u128;
 
     fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
         Ok(self.p1)
@@ -133,4 +222,59 @@ mod tests {
         let solution = Lobby::solve(input).unwrap();
         assert_eq!(solution, Solution::new(357, 3121910778619));
     }
+
+    #[test]
+    fn any_k() {
+        // compare against trying every subset of small banks
+        let mut state = 0x1234_5678_9abc_def0_u64;
+        for _ in 0..500 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let len = (state % 12) as usize + 1;
+            let bank: String = (0..len)
+                .map(|i| (b'1' + ((state >> (4 + i * 3)) % 9) as u8) as char)
+                .collect();
+
+            for k in 0..=len {
+                let best = (0_u32..(1 << len))
+                    .filter(|mask| mask.count_ones() as usize == k)
+                    .map(|mask| {
+                        bank.chars()
+                            .enumerate()
+                            .filter(|(i, _)| mask & (1 << i) != 0)
+                            .fold(0_u128, |acc, (_, c)| acc * 10 + (c as u8 - b'0') as u128)
+                    })
+                    .max()
+                    .unwrap();
+
+                let joltage = best_joltage(&bank, k).unwrap();
+                assert_eq!(joltage.value(), Some(best), "{} {}", bank, k);
+                for max_digit in [scan::max_digit_scalar, scan::max_digit_swar] {
+                    assert_eq!(best_joltage_by(&bank, k, max_digit).unwrap(), joltage);
+                }
+                assert_eq!(joltage.indices().len(), k);
+                assert!(joltage.indices().windows(2).all(|w| w[0] < w[1]));
+                assert!(
+                    joltage
+                        .indices()
+                        .iter()
+                        .zip(joltage.as_str().bytes())
+                        .all(|(i, b)| bank.as_bytes()[*i] == b)
+                );
+            }
+        }
+
+        // too many digits for a u128, but still fine as a string
+        let bank = "9".repeat(50);
+        let joltage = best_joltage(&bank, 40).unwrap();
+        assert_eq!(joltage.value(), None);
+        assert_eq!(joltage.as_str(), "9".repeat(40));
+
+        assert!(best_joltage("123", 4).is_err());
+        assert!(best_joltage("12a3", 2).is_err());
+        assert!(best_joltage_by("123", 4, scan::max_digit_swar).is_err());
+        assert!(best_joltage_by("12a3", 2, scan::max_digit_swar).is_err());
+        assert!(Lobby::solve("12345").is_err());
+    }
 }
@@ -0,0 +1,121 @@
+//! Finding the first largest digit in a run of ascii digits.
+//!
+//! Both scanners return the same thing, the SWAR one just looks at 16 bytes at
+//! a time by treating them as a single `u128`. They're used with
+//! [`best_joltage_by`](crate::best_joltage_by), which the solver only does
+//! with the `swar` feature.
+
+/// The index and value (as an ascii digit) of the first largest digit in
+/// `digits`, stopping early at the first `9`.
+///
+/// ```
+/// use lobby::scan;
+///
+/// assert_eq!(scan::max_digit_scalar(b"3141592653"), Some((5, b'9')));
+/// assert_eq!(scan::max_digit_scalar(b""), None);
+/// ```
+pub fn max_digit_scalar(digits: &[u8]) -> Option<(usize, u8)> {
+    let mut best: Option<(usize, u8)> = None;
+    for (idx, d) in digits.iter().enumerate() {
+        if best.is_none_or(|(_, b)| *d > b) {
+            best = Some((idx, *d));
+            if *d == b'9' {
+                break;
+            }
+        }
+    }
+    best
+}
+
+const ONES: u128 = u128::from_le_bytes([0x01; 16]);
+const HIGHS: u128 = u128::from_le_bytes([0x80; 16]);
+
+/// The same as [`max_digit_scalar`], but skipping 16 bytes at a time past any
+/// that can't beat the largest digit so far.
+///
+/// Each word is only looked at once: a word with a larger digit somewhere in
+/// it is searched byte by byte, which happens at most once per distinct digit
+/// value, and a `9` stops the scan.
+///
+/// ```
+/// use lobby::scan;
+///
+/// let digits = b"1111111111111111111111111111111811111111";
+/// assert_eq!(scan::max_digit_swar(digits), Some((31, b'8')));
+/// assert_eq!(scan::max_digit_swar(digits), scan::max_digit_scalar(digits));
+/// ```
+pub fn max_digit_swar(digits: &[u8]) -> Option<(usize, u8)> {
+    let mut best: Option<(usize, u8)> = None;
+
+    let mut chunks = digits.chunks_exact(16);
+    for (idx, chunk) in chunks.by_ref().enumerate() {
+        let word = u128::from_le_bytes(chunk.try_into().expect("chunks are 16 bytes"));
+        if best.is_some_and(|(_, b)| !has_greater(word, b)) {
+            continue;
+        }
+
+        // the first largest digit here is larger than anything before it
+        if let Some((offset, d)) = max_digit_scalar(chunk) {
+            best = Some((idx * 16 + offset, d));
+            if d == b'9' {
+                return best;
+            }
+        }
+    }
+
+    let rem = chunks.remainder();
+    let base = digits.len() - rem.len();
+    match max_digit_scalar(rem) {
+        Some((offset, d)) if best.is_none_or(|(_, b)| d > b) => Some((base + offset, d)),
+        _ => best,
+    }
+}
+
+/// Whether any byte of `word` is greater than `n`, for bytes below `0x80`
+/// (which every ascii digit is).
+///
+/// Adding `0x7f - n` to each byte carries into its high bit exactly when the
+/// byte is greater than `n`, and never past it into the next byte.
+fn has_greater(word: u128, n: u8) -> bool {
+    let bias = ONES * (0x7f - n.min(0x7f)) as u128;
+    (word.wrapping_add(bias) | word) & HIGHS != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scanners_agree() {
+        let mut state = 0xdead_beef_cafe_f00d_u64;
+        for len in 0..100 {
+            for _ in 0..20 {
+                let digits: Vec<u8> = (0..len)
+                    .map(|_| {
+                        state ^= state << 13;
+                        state ^= state >> 7;
+                        state ^= state << 17;
+                        // mostly small digits so the max is somewhere later
+                        b'0' + (state % 10).min(state % 7) as u8
+                    })
+                    .collect();
+
+                assert_eq!(max_digit_swar(&digits), max_digit_scalar(&digits));
+            }
+        }
+    }
+
+    #[test]
+    fn greater_bytes() {
+        for n in b'0'..=b'9' {
+            for d in b'0'..=b'9' {
+                for pos in 0..16 {
+                    let mut word = [b'0'; 16];
+                    word[pos] = d;
+                    let word = u128::from_le_bytes(word);
+                    assert_eq!(has_greater(word, n), d > n);
+                }
+            }
+        }
+    }
+}
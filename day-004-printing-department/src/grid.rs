@@ -0,0 +1,174 @@
+//! A grid of bits sized at runtime.
+use std::{fmt::Display, str::FromStr};
+
+use anyhow::bail;
+
+/// Each row is stored as `u64` words with an empty border cell on every side,
+/// so neighbor counts never have to special case the edges.
+///
+/// ```
+/// use printing_department::grid::BitGrid;
+///
+/// let grid: BitGrid = "@@.\n.@@".parse().unwrap();
+/// assert_eq!((grid.width(), grid.height()), (3, 2));
+/// assert_eq!(grid.count_ones(), 4);
+/// assert_eq!(grid.neighbors(0, 1), 3);
+/// assert_eq!(grid.iter().collect::<Vec<_>>(), [(0, 0), (0, 1), (1, 1), (1, 2)]);
+/// assert_eq!(grid.to_string(), "@@.\n.@@\n");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    /// The number of words per (padded) row.
+    stride: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        let stride = (width + 2).div_ceil(64);
+        Self {
+            width,
+            height,
+            stride,
+            words: vec![0; stride * (height + 2)],
+        }
+    }
+
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The word and bit for the given cell, which may be in the border.
+    fn index(&self, padded_row: usize, padded_col: usize) -> (usize, usize) {
+        (padded_row * self.stride + padded_col / 64, padded_col % 64)
+    }
+
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        if row >= self.height || col >= self.width {
+            return false;
+        }
+        let (word, bit) = self.index(row + 1, col + 1);
+        self.words[word] & (1 << bit) != 0
+    }
+
+    /// Set the given cell.
+    ///
+    /// # Panics
+    /// If the cell is outside of the grid.
+    pub fn insert(&mut self, row: usize, col: usize) {
+        assert!(row < self.height && col < self.width, "out of bounds");
+        let (word, bit) = self.index(row + 1, col + 1);
+        self.words[word] |= 1 << bit;
+    }
+
+    /// Clear the given cell, if it's in the grid.
+    pub fn remove(&mut self, row: usize, col: usize) {
+        if row < self.height && col < self.width {
+            let (word, bit) = self.index(row + 1, col + 1);
+            self.words[word] &= !(1 << bit);
+        }
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// The three bits centered on `padded_col` in `padded_row`.
+    fn window(&self, padded_row: usize, padded_col: usize) -> u64 {
+        let (word, bit) = self.index(padded_row, padded_col - 1);
+        let mut bits = self.words[word] >> bit;
+        if bit > 61 {
+            // the window straddles two words, and the border guarantees the
+            // next one exists
+            bits |= self.words[word + 1] << (64 - bit);
+        }
+        bits & 0b111
+    }
+
+    /// How many of the 8 cells around the given one are set.
+    ///
+    /// This masks out three cells from each of the rows above, at and below
+    /// the cell, so it's a handful of popcounts rather than 8 lookups.
+    pub fn neighbors(&self, row: usize, col: usize) -> u32 {
+        let center = self.contains(row, col) as u32;
+        let (row, col) = (row + 1, col + 1);
+        let count = self.window(row - 1, col).count_ones()
+            + self.window(row, col).count_ones()
+            + self.window(row + 1, col).count_ones();
+
+        count - center
+    }
+
+    /// Every set cell as `(row, col)`, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.height).flat_map(move |row| {
+            let start = (row + 1) * self.stride;
+            self.words[start..start + self.stride]
+                .iter()
+                .enumerate()
+                .flat_map(move |(idx, word)| {
+                    let mut word = *word;
+                    std::iter::from_fn(move || {
+                        if word == 0 {
+                            return None;
+                        }
+                        let bit = word.trailing_zeros() as usize;
+                        word &= word - 1;
+                        Some((row, idx * 64 + bit - 1))
+                    })
+                })
+        })
+    }
+}
+
+impl FromStr for BitGrid {
+    type Err = anyhow::Error;
+
+    /// Parse rows of `@` (set) and `.` (clear), which must all be the same
+    /// length.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = s.trim().lines().collect();
+        let width = lines.first().map(|l| l.len()).unwrap_or_default();
+
+        let mut grid = Self::new(width, lines.len());
+        for (row, line) in lines.iter().enumerate() {
+            if line.len() != width {
+                bail!(
+                    "row {} has {} cells, but the first row has {}",
+                    row,
+                    line.len(),
+                    width
+                );
+            }
+
+            for (col, ch) in line.bytes().enumerate() {
+                match ch {
+                    b'@' => grid.insert(row, col),
+                    b'.' => {}
+                    _ => bail!("invalid cell {:?} in row {}", ch as char, row),
+                }
+            }
+        }
+
+        Ok(grid)
+    }
+}
+
+impl Display for BitGrid {
+    /// The grid in the same format it's parsed from.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in 0..self.height {
+            for col in 0..self.width {
+                f.write_str(if self.contains(row, col) { "@" } else { "." })?;
+            }
+            f.write_str("\n")?;
+        }
+        Ok(())
+    }
+}
@@ -1,97 +1,59 @@
-use std::{collections::VecDeque, str::FromStr};
+use std::str::FromStr;
 
-use aoc_plumbing::Problem;
-use aoc_std::{collections::BitSet, geometry::Location};
+use aoc_plumbing::{
+    Problem,
+    viz::{Paint, Scene, Visualize},
+};
 
-pub type PrintingDepartment = PrintingDepartmentGen<3>;
+use crate::{grid::BitGrid, rule::Rule};
+
+pub mod grid;
+pub mod rule;
 
 #[derive(Debug, Clone)]
-pub struct PrintingDepartmentGen<const M: usize> {
+pub struct PrintingDepartment {
     p1: usize,
     p2: usize,
 }
 
-impl<const M: usize> FromStr for PrintingDepartmentGen<M> {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut p1 = 0;
-
-        let height = s.trim().lines().count();
-        // let mut removed = VecDeque::default();
-        let mut removed = VecDeque::default();
-        let mut grid = vec![BitSet::<M>::ZERO; height + 2];
-
-        let mut width = 0;
-
-        for (row, line) in s.trim().lines().enumerate() {
-            width = line.len();
-            for (col, ch) in line.as_bytes().iter().enumerate() {
-                if ch == &b'@' {
-                    grid[row + 1].insert(col + 1);
-                }
-            }
-        }
+impl PrintingDepartment {
+    /// Solve with a different accessibility rule than the puzzle's.
+    ///
+    /// ```
+    /// use aoc_plumbing::Problem;
+    /// use printing_department::{
+    ///     PrintingDepartment,
+    ///     rule::{Neighborhood, Rule},
+    /// };
+    ///
+    /// let rule = Rule {
+    ///     threshold: 2,
+    ///     neighborhood: Neighborhood::VonNeumann,
+    ///     wrap: false,
+    /// };
+    /// let mut dept = PrintingDepartment::with_rule("@@@\n@.@\n@@@", rule).unwrap();
+    /// assert_eq!(dept.part_one().unwrap(), 0);
+    /// ```
+    pub fn with_rule(input: &str, rule: Rule) -> anyhow::Result<Self> {
+        let grid: BitGrid = input.parse()?;
+        let peel = rule.peel(&grid);
 
-        let mut seen = vec![vec![0_u8; width]; height];
-        let mut masks = vec![BitSet::<M>::zero(); width + 2];
-
-        let mut base_mask = BitSet::<M>::zero();
-        base_mask.set_lower(0b111);
-
-        #[allow(clippy::needless_range_loop)]
-        for col in 1..(width + 1) {
-            masks[col] = base_mask << (col - 1);
-        }
-
-        for row in 1..(height + 1) {
-            let mut col = 0;
-            while let Some(next) = grid[row].next_beyond(col) {
-                col = next;
-
-                if col > height {
-                    break;
-                }
-
-                let count = (grid[row] & masks[col]).count()
-                    + (grid[row - 1] & masks[col]).count()
-                    + (grid[row + 1] & masks[col]).count()
-                    - 1;
-
-                let loc = Location::new(row - 1, col - 1);
-                seen[loc.row][loc.col] = count as u8;
-                if count < 4 {
-                    p1 += 1;
-                    removed.push_back(loc);
-                }
-            }
-        }
+        Ok(Self {
+            p1: peel.accessible,
+            p2: peel.removed,
+        })
+    }
+}
 
-        let mut p2 = 0;
-
-        while let Some(loc) = removed.pop_back() {
-            seen[loc.row][loc.col] = 0;
-            p2 += 1;
-
-            for (_, neighbor) in loc
-                .neighbors()
-                .filter(|(_, n)| n.col < width && n.row < height)
-            {
-                let val = seen[neighbor.row][neighbor.col];
-                if val == 4 {
-                    seen[neighbor.row][neighbor.col] -= 1;
-                    removed.push_back(neighbor);
-                } else if val > 1 {
-                    seen[neighbor.row][neighbor.col] -= 1;
-                }
-            }
-        }
+impl FromStr for PrintingDepartment {
+    type Err = anyhow::Error;
 
-        Ok(Self { p1, p2 })
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::with_rule(s, Rule::default())
     }
 }
 
-impl<const M: usize> Problem for PrintingDepartmentGen<M> {
+impl Problem for PrintingDepartment {
     const DAY: usize = 4;
     const TITLE: &'static str = "printing department";
     const README: &'static str = include_str!("../README.md");
@@ -109,11 +71,68 @@ impl<const M: usize> Problem for PrintingDepartmentGen<M> {
     }
 }
 
+impl Visualize for PrintingDepartment {
+    /// Every roll, colored by whether it's accessible right away, removed
+    /// eventually, or stuck for good.
+    fn visualize(raw_input: &str) -> Result<Scene, Self::ProblemError> {
+        let grid: BitGrid = raw_input.parse()?;
+        let waves = Rule::default().waves(&grid);
+
+        let mut scene = Scene::new(grid.width() as f64, grid.height() as f64);
+        for (generation, wave) in waves.waves().iter().enumerate() {
+            let paint = if generation == 0 {
+                Paint::Highlight
+            } else {
+                Paint::Primary
+            };
+            for (row, col) in wave {
+                scene.cell(*col, *row, paint);
+            }
+        }
+        for (row, col) in waves.remaining().iter() {
+            scene.cell(col, row, Paint::Muted);
+        }
+        scene.legend(Paint::Highlight, "accessible (part 1)");
+        scene.legend(Paint::Primary, "removed later (part 2)");
+        scene.legend(Paint::Muted, "never removed");
+
+        Ok(scene)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use aoc_plumbing::Solution;
+    use aoc_plumbing::{Solution, viz::Shape};
 
     use super::*;
+    use crate::rule::Neighborhood;
+
+    /// Remove every accessible roll at once, over and over.
+    fn brute_force(input: &str, rule: Rule) -> (usize, usize) {
+        let mut grid: BitGrid = input.parse().unwrap();
+        let mut accessible = None;
+        let mut removed = 0;
+        loop {
+            let wave: Vec<_> = grid
+                .iter()
+                .filter(|(row, col)| {
+                    let count = rule
+                        .neighbors(&grid, *row, *col)
+                        .filter(|(r, c)| grid.contains(*r, *c))
+                        .count();
+                    count < rule.threshold as usize
+                })
+                .collect();
+            accessible.get_or_insert(wave.len());
+            if wave.is_empty() {
+                return (accessible.unwrap_or_default(), removed);
+            }
+            removed += wave.len();
+            for (row, col) in wave {
+                grid.remove(row, col);
+            }
+        }
+    }
 
     #[test]
     #[ignore]
@@ -135,7 +154,131 @@ mod tests {
 @.@@@.@@@@
 .@@@@@@@@.
 @.@.@@@.@.";
-        let solution = PrintingDepartmentGen::<1>::solve(input).unwrap();
+        let solution = PrintingDepartment::solve(input).unwrap();
         assert_eq!(solution, Solution::new(13, 43));
+
+        let scene = PrintingDepartment::visualize(input).unwrap();
+        let count = |paint| {
+            scene
+                .shapes
+                .iter()
+                .filter(|s| matches!(s, Shape::Cell { paint: p, .. } if *p == paint))
+                .count()
+        };
+        assert_eq!(count(Paint::Highlight), 13);
+        assert_eq!(count(Paint::Highlight) + count(Paint::Primary), 43);
+        assert_eq!(count(Paint::Muted), input.matches('@').count() - 43);
+    }
+
+    #[test]
+    fn example_waves() {
+        let input = "..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.";
+        let grid: BitGrid = input.parse().unwrap();
+        let waves = Rule::default().waves(&grid);
+        assert_eq!(
+            waves.counts().collect::<Vec<_>>(),
+            [13, 12, 7, 5, 2, 1, 1, 1, 1]
+        );
+        assert_eq!(waves.removed(), 43);
+        assert_eq!(waves.after(0), grid);
+        assert_eq!(&waves.after(waves.waves().len()), waves.remaining());
+        assert_eq!(
+            waves.after(1).to_string(),
+            ".......@..
+.@@.@.@.@@
+@@@@@...@@
+@.@@@@..@.
+.@.@@@@.@.
+.@@@@@@@.@
+.@.@.@.@@@
+..@@@.@@@@
+.@@@@@@@@.
+....@@@...
+"
+        );
+        assert_eq!(
+            waves.remaining().to_string(),
+            "..........
+..........
+..........
+....@@....
+...@@@@...
+...@@@@@..
+...@.@.@@.
+...@@.@@@.
+...@@@@@..
+....@@@...
+"
+        );
+    }
+
+    #[test]
+    fn wide_grids() {
+        // wider than the three words the rows used to be limited to
+        let row = "@".repeat(300);
+        let solution = PrintingDepartment::solve(&row).unwrap();
+        assert_eq!(solution, Solution::new(300, 300));
+
+        // only the corners start out accessible, but everything peels off
+        let solution = PrintingDepartment::solve(&format!("{}\n{}", row, row)).unwrap();
+        assert_eq!(solution, Solution::new(4, 600));
+
+        assert!(PrintingDepartment::solve("@@\n@").is_err());
+        assert!(PrintingDepartment::solve("@x").is_err());
+    }
+
+    #[test]
+    fn rules() {
+        let mut state = 0x1234_5678_9abc_def0_u64;
+        let mut inputs = vec!["@".to_string(), "@@".to_string(), "@\n@".to_string()];
+        for (width, height) in [(5, 5), (9, 4), (70, 6), (2, 30)] {
+            for _ in 0..10 {
+                let rows: Vec<String> = (0..height)
+                    .map(|_| {
+                        (0..width)
+                            .map(|_| {
+                                state ^= state << 13;
+                                state ^= state >> 7;
+                                state ^= state << 17;
+                                if state.is_multiple_of(4) { '.' } else { '@' }
+                            })
+                            .collect()
+                    })
+                    .collect();
+                inputs.push(rows.join("\n"));
+            }
+        }
+
+        for input in &inputs {
+            let grid: BitGrid = input.parse().unwrap();
+            for neighborhood in [Neighborhood::Moore, Neighborhood::VonNeumann] {
+                for wrap in [false, true] {
+                    for threshold in 0..=9 {
+                        let rule = Rule {
+                            threshold,
+                            neighborhood,
+                            wrap,
+                        };
+                        let peel = rule.peel(&grid);
+                        assert_eq!(
+                            (peel.accessible, peel.removed),
+                            brute_force(input, rule),
+                            "{:?} on\n{}",
+                            rule,
+                            input
+                        );
+                    }
+                }
+            }
+        }
     }
 }
@@ -0,0 +1,292 @@
+//! Which rolls count as accessible, and peeling them off until none are left.
+use std::collections::VecDeque;
+
+use crate::grid::BitGrid;
+
+const MOORE: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+const VON_NEUMANN: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// The cells that count as next to a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Neighborhood {
+    /// All 8 surrounding cells.
+    #[default]
+    Moore,
+    /// Only the 4 orthogonally adjacent cells.
+    VonNeumann,
+}
+
+impl Neighborhood {
+    pub fn offsets(&self) -> &'static [(isize, isize)] {
+        match self {
+            Self::Moore => &MOORE,
+            Self::VonNeumann => &VON_NEUMANN,
+        }
+    }
+}
+
+/// A roll is accessible when fewer than `threshold` of its neighbors are
+/// rolls.
+///
+/// With `wrap`, the grid is a torus, so the cells on opposite edges are
+/// neighbors. On a torus less than 3 cells across, the same cell can be a
+/// neighbor more than once (or even a neighbor of itself), and is counted
+/// each time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule {
+    pub threshold: u32,
+    pub neighborhood: Neighborhood,
+    pub wrap: bool,
+}
+
+impl Default for Rule {
+    /// The rule from the puzzle: fewer than 4 of the 8 surrounding cells.
+    fn default() -> Self {
+        Self {
+            threshold: 4,
+            neighborhood: Neighborhood::Moore,
+            wrap: false,
+        }
+    }
+}
+
+/// The result of peeling with a [`Rule`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Peel {
+    /// How many rolls were accessible to begin with.
+    pub accessible: usize,
+    /// How many rolls were removed in total.
+    pub removed: usize,
+}
+
+impl Rule {
+    /// The neighbors of the given cell that are in the grid.
+    pub fn neighbors(
+        &self,
+        grid: &BitGrid,
+        row: usize,
+        col: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + use<> {
+        let height = grid.height() as isize;
+        let width = grid.width() as isize;
+        let wrap = self.wrap;
+
+        self.neighborhood
+            .offsets()
+            .iter()
+            .filter_map(move |(dr, dc)| {
+                let r = row as isize + dr;
+                let c = col as isize + dc;
+                if wrap {
+                    Some((r.rem_euclid(height) as usize, c.rem_euclid(width) as usize))
+                } else if (0..height).contains(&r) && (0..width).contains(&c) {
+                    Some((r as usize, c as usize))
+                } else {
+                    None
+                }
+            })
+    }
+
+    /// How many of the neighbors of the given cell are set.
+    pub fn count(&self, grid: &BitGrid, row: usize, col: usize) -> u32 {
+        if self.neighborhood == Neighborhood::Moore && !self.wrap {
+            // the common case gets to use the bitmask trick
+            return grid.neighbors(row, col);
+        }
+
+        self.neighbors(grid, row, col)
+            .filter(|(r, c)| grid.contains(*r, *c))
+            .count() as u32
+    }
+
+    /// Repeatedly remove accessible rolls until none are left.
+    ///
+    /// Each roll's neighbor count is computed once, then decremented as its
+    /// neighbors are removed, so a roll is queued exactly when its count drops
+    /// below the threshold. The order rolls are removed in doesn't change
+    /// which are removed in the end.
+    ///
+    /// ```
+    /// use printing_department::{
+    ///     grid::BitGrid,
+    ///     rule::{Neighborhood, Peel, Rule},
+    /// };
+    ///
+    /// let grid: BitGrid = "@@@\n@@@\n@@@".parse().unwrap();
+    /// assert_eq!(Rule::default().peel(&grid), Peel { accessible: 4, removed: 9 });
+    ///
+    /// // on a torus every cell has all 8 neighbors
+    /// let rule = Rule { wrap: true, ..Default::default() };
+    /// assert_eq!(rule.peel(&grid), Peel { accessible: 0, removed: 0 });
+    ///
+    /// let rule = Rule { threshold: 3, neighborhood: Neighborhood::VonNeumann, wrap: false };
+    /// assert_eq!(rule.peel(&grid), Peel { accessible: 4, removed: 9 });
+    /// ```
+    pub fn peel(&self, grid: &BitGrid) -> Peel {
+        let mut state = self.start(grid);
+        let mut queue: VecDeque<_> = state.accessible.iter().copied().collect();
+        let accessible = queue.len();
+        let mut removed = 0;
+
+        while let Some((row, col)) = queue.pop_back() {
+            removed += 1;
+            aoc_plumbing::trace!("removed", row = row, col = col, total = removed);
+            self.release(grid, &mut state, row, col, |cell| queue.push_back(cell));
+        }
+
+        Peel {
+            accessible,
+            removed,
+        }
+    }
+
+    /// The same as [`Rule::peel`], but removing every accessible roll at once,
+    /// one generation after another, like the puzzle describes.
+    ///
+    /// ```
+    /// use printing_department::{grid::BitGrid, rule::Rule};
+    ///
+    /// let grid: BitGrid = "@@@@\n@@@@\n@@@@".parse().unwrap();
+    /// let waves = Rule::default().waves(&grid);
+    /// assert_eq!(waves.counts().collect::<Vec<_>>(), [4, 2, 4, 2]);
+    /// assert_eq!(waves.waves()[0], [(0, 0), (0, 3), (2, 0), (2, 3)]);
+    /// assert_eq!(waves.after(1).to_string(), ".@@.\n@@@@\n.@@.\n");
+    /// assert_eq!(waves.remaining().count_ones(), 0);
+    /// ```
+    pub fn waves(&self, grid: &BitGrid) -> Waves {
+        let mut state = self.start(grid);
+        let mut wave = std::mem::take(&mut state.accessible);
+        let mut waves = Vec::default();
+        let mut remaining = grid.clone();
+
+        while !wave.is_empty() {
+            aoc_plumbing::trace!("wave", generation = waves.len() + 1, count = wave.len());
+
+            let mut next = Vec::default();
+            for (row, col) in wave.iter() {
+                remaining.remove(*row, *col);
+                self.release(grid, &mut state, *row, *col, |cell| next.push(cell));
+            }
+
+            wave.sort_unstable();
+            waves.push(std::mem::replace(&mut wave, next));
+        }
+
+        Waves {
+            initial: grid.clone(),
+            waves,
+            remaining,
+        }
+    }
+
+    /// Count the neighbors of every roll, finding the ones that start out
+    /// accessible.
+    fn start(&self, grid: &BitGrid) -> PeelState {
+        let width = grid.width();
+        let mut state = PeelState {
+            width,
+            counts: vec![0; width * grid.height()],
+            alive: grid.clone(),
+            accessible: Vec::default(),
+        };
+
+        for (row, col) in grid.iter() {
+            let count = self.count(grid, row, col);
+            state.counts[row * width + col] = count;
+            if count < self.threshold {
+                aoc_plumbing::trace!("accessible", row = row, col = col, neighbors = count);
+                state.alive.remove(row, col);
+                state.accessible.push((row, col));
+            }
+        }
+
+        state
+    }
+
+    /// Remove a roll from its neighbors' counts, passing each neighbor that
+    /// becomes accessible because of it to `push`.
+    ///
+    /// A roll's count only drops below the threshold once, so every roll is
+    /// pushed at most once.
+    fn release(
+        &self,
+        grid: &BitGrid,
+        state: &mut PeelState,
+        row: usize,
+        col: usize,
+        mut push: impl FnMut((usize, usize)),
+    ) {
+        for (r, c) in self.neighbors(grid, row, col) {
+            if !state.alive.contains(r, c) {
+                continue;
+            }
+
+            let count = &mut state.counts[r * state.width + c];
+            *count -= 1;
+            if *count + 1 == self.threshold {
+                state.alive.remove(r, c);
+                push((r, c));
+            }
+        }
+    }
+}
+
+struct PeelState {
+    width: usize,
+    /// The neighbor counts of every roll, as of the rolls removed so far.
+    counts: Vec<u32>,
+    /// The rolls that haven't been found accessible yet.
+    alive: BitGrid,
+    /// The rolls that are accessible before anything is removed.
+    accessible: Vec<(usize, usize)>,
+}
+
+/// The generations of rolls removed by [`Rule::waves`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Waves {
+    initial: BitGrid,
+    waves: Vec<Vec<(usize, usize)>>,
+    remaining: BitGrid,
+}
+
+impl Waves {
+    /// The `(row, col)` of every roll removed in each generation, in reading
+    /// order. The first generation is the rolls that are accessible right
+    /// away.
+    pub fn waves(&self) -> &[Vec<(usize, usize)>] {
+        &self.waves
+    }
+
+    /// How many rolls were removed in each generation.
+    pub fn counts(&self) -> impl Iterator<Item = usize> + '_ {
+        self.waves.iter().map(|w| w.len())
+    }
+
+    /// How many rolls were removed in total.
+    pub fn removed(&self) -> usize {
+        self.counts().sum()
+    }
+
+    /// The grid after the first `generations` waves have been removed.
+    pub fn after(&self, generations: usize) -> BitGrid {
+        let mut grid = self.initial.clone();
+        for (row, col) in self.waves.iter().take(generations).flatten() {
+            grid.remove(*row, *col);
+        }
+        grid
+    }
+
+    /// The stable grid left once nothing else can be removed.
+    pub fn remaining(&self) -> &BitGrid {
+        &self.remaining
+    }
+}
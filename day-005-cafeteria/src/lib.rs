@@ -1,13 +1,32 @@
-use std::{ops::RangeInclusive, str::FromStr};
+use std::str::FromStr;
 
 use anyhow::anyhow;
 
 use aoc_plumbing::Problem;
 
+use crate::range_set::RangeSet;
+
+pub mod range_set;
+
 #[derive(Debug, Clone)]
 pub struct Cafeteria {
+    fresh: RangeSet<u64>,
     p1: usize,
-    p2: u64,
+    p2: u128,
+}
+
+impl Cafeteria {
+    /// The fresh ingredient ids, merged into as few ranges as possible.
+    ///
+    /// ```
+    /// use cafeteria::Cafeteria;
+    ///
+    /// let cafeteria: Cafeteria = "3-5\n10-14\n16-20\n12-18\n6-6\n\n1".parse().unwrap();
+    /// assert_eq!(cafeteria.fresh().as_slice(), [3..=6, 10..=20]);
+    /// ```
+    pub fn fresh(&self) -> &RangeSet<u64> {
+        &self.fresh
+    }
 }
 
 impl FromStr for Cafeteria {
@@ -19,80 +38,31 @@ impl FromStr for Cafeteria {
             .split_once("\n\n")
             .ok_or_else(|| anyhow!("invalid input"))?;
 
-        let mut ranges: Vec<RangeInclusive<u64>> = Vec::default();
-
+        let mut fresh: RangeSet<u64> = RangeSet::default();
         for line in raw_ranges.lines() {
             let (start, end) = line
                 .split_once("-")
                 .ok_or_else(|| anyhow!("invalid input"))?;
-            ranges.push(start.parse()?..=end.parse()?);
+            fresh.insert(start.parse()?..=end.parse()?);
         }
 
-        // sort this reversed
-        ranges.sort_unstable_by(|a, b| b.start().cmp(a.start()));
-
-        let mut merged = Vec::with_capacity(ranges.len());
-
-        let mut cur = ranges.pop().ok_or_else(|| anyhow!("invalid input"))?;
-
-        let mut p2 = 0;
-        while let Some(next) = ranges.pop() {
-            if cur.contains(next.start()) {
-                cur = (*cur.start()).min(*next.start())..=(*cur.end()).max(*next.end());
-            } else {
-                p2 += cur.end() - cur.start() + 1;
-                merged.push(cur);
-                cur = next;
-            }
+        if fresh.is_empty() {
+            return Err(anyhow!("invalid input"));
         }
 
-        p2 += cur.end() - cur.start() + 1;
-        merged.push(cur);
-
-        let mut ids = raw_ids
-            .lines()
-            .map(u64::from_str)
-            .collect::<Result<Vec<_>, _>>()?;
-        ids.sort();
+        for range in fresh.iter() {
+            aoc_plumbing::trace!("merged", start = range.start(), end = range.end());
+        }
+        let p2 = fresh.len().ok_or_else(|| anyhow!("too many fresh ids"))?;
 
         let mut p1 = 0;
-        let mut lower = 0;
-        for id in ids.iter() {
-            if id < merged[0].start() || id > merged[merged.len() - 1].end() {
-                continue;
-            }
-
-            // if we're sorted, we might just exist here already
-            if merged[lower].contains(id) {
+        for line in raw_ids.lines() {
+            if fresh.contains(line.parse()?) {
                 p1 += 1;
-                continue;
-            }
-
-            let mut left = lower;
-            let mut right = merged.len() - 1;
-
-            while left <= right {
-                let mid = left + ((right - left) / 2);
-                let cur = &merged[mid];
-
-                if cur.contains(id) {
-                    p1 += 1;
-                    // since ids is sorted, narrow the range for the next id
-                    lower = mid;
-                    break;
-                }
-
-                if cur.end() < id {
-                    left = mid + 1;
-                } else if cur.start() > id && mid > 0 {
-                    right = mid - 1;
-                } else {
-                    break;
-                }
             }
         }
 
-        Ok(Self { p1, p2 })
+        Ok(Self { fresh, p1, p2 })
     }
 }
 
@@ -103,7 +73,7 @@ impl Problem for Cafeteria {
 
     type ProblemError = anyhow::Error;
     type P1 = usize;
-    type P2 = u64;
+    type P2 = u128;
 
     fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
         Ok(self.p1)
@@ -143,5 +113,23 @@ mod tests {
 32";
         let solution = Cafeteria::solve(input).unwrap();
         assert_eq!(solution, Solution::new(3, 14));
+
+        let cafeteria: Cafeteria = input.parse().unwrap();
+        assert_eq!(cafeteria.fresh().as_slice(), [3..=5, 10..=20]);
+    }
+
+    #[test]
+    fn merging() {
+        // adjacent ranges are merged, even though it doesn't change the count
+        let cafeteria: Cafeteria = "3-5\n6-8\n10-10\n\n1".parse().unwrap();
+        assert_eq!(cafeteria.fresh().as_slice(), [3..=8, 10..=10]);
+
+        let solution = Cafeteria::solve("3-5\n6-8\n10-10\n\n5\n6\n9").unwrap();
+        assert_eq!(solution, Solution::new(2, 7));
+
+        // every possible id
+        let input = format!("0-{}\n5-6\n\n0\n{}", u64::MAX, u64::MAX);
+        let solution = Cafeteria::solve(&input).unwrap();
+        assert_eq!(solution, Solution::new(2, 1 << 64));
     }
 }
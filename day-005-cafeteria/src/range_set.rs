@@ -0,0 +1,384 @@
+//! A set of values stored as sorted, disjoint inclusive ranges.
+use std::{fmt::Debug, ops::RangeInclusive};
+
+/// The integer types a [`RangeSet`] can hold.
+pub trait Bound: Copy + Ord + Debug {
+    /// The value right before this one, if there is one.
+    fn pred(self) -> Option<Self>;
+
+    /// The value right after this one, if there is one.
+    fn succ(self) -> Option<Self>;
+
+    /// How many values are in `start..=end`, if that fits in a `u128`.
+    ///
+    /// Only the full range of a 128-bit type doesn't.
+    fn count(start: Self, end: Self) -> Option<u128>;
+}
+
+macro_rules! impl_bound {
+    ($($t:ty),*) => {
+        $(
+            impl Bound for $t {
+                fn pred(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn succ(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn count(start: Self, end: Self) -> Option<u128> {
+                    (end.abs_diff(start) as u128).checked_add(1)
+                }
+            }
+        )*
+    };
+}
+
+impl_bound!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+/// Ranges that overlap or are adjacent (like `3..=5` and `6..=8`) are merged
+/// as they're inserted, so iterating yields them in order with at least one
+/// missing value between each. That makes the ranges the only way to represent
+/// a given set of values, so two sets are equal exactly when they contain the
+/// same values.
+///
+/// ```
+/// use cafeteria::range_set::RangeSet;
+///
+/// let mut set: RangeSet<u64> = [3..=5, 10..=14, 16..=20, 12..=18, 6..=7].into_iter().collect();
+/// assert_eq!(set.as_slice(), [3..=7, 10..=20]);
+/// assert_eq!(set.len(), Some(16));
+/// assert!(set.contains(17));
+/// assert!(!set.contains(8));
+///
+/// set.remove(4..=12);
+/// assert_eq!(set.as_slice(), [3..=3, 13..=20]);
+///
+/// let everything: RangeSet<u64> = [0..=u64::MAX].into_iter().collect();
+/// assert_eq!(everything.len(), Some(1 << 64));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    ranges: Vec<RangeInclusive<T>>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self {
+            ranges: Vec::default(),
+        }
+    }
+}
+
+impl<T: Bound> RangeSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of merged ranges.
+    pub fn range_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The total number of values in the set, or `None` if that doesn't fit
+    /// in a `u128`.
+    pub fn len(&self) -> Option<u128> {
+        self.ranges.iter().try_fold(0_u128, |total, r| {
+            total.checked_add(T::count(*r.start(), *r.end())?)
+        })
+    }
+
+    /// The merged ranges, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &RangeInclusive<T>> {
+        self.ranges.iter()
+    }
+
+    /// The merged ranges, in order.
+    pub fn as_slice(&self) -> &[RangeInclusive<T>] {
+        &self.ranges
+    }
+
+    pub fn into_vec(self) -> Vec<RangeInclusive<T>> {
+        self.ranges
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let idx = self.ranges.partition_point(|r| *r.end() < value);
+        self.ranges.get(idx).is_some_and(|r| *r.start() <= value)
+    }
+
+    /// The indices of the ranges that share a value with `start..=end`.
+    fn overlapping(&self, start: T, end: T) -> std::ops::Range<usize> {
+        let lo = self.ranges.partition_point(|r| *r.end() < start);
+        let hi = self.ranges.partition_point(|r| *r.start() <= end);
+        lo..hi
+    }
+
+    /// The indices of the ranges that share a value with, or are adjacent to,
+    /// `start..=end`.
+    fn touching(&self, start: T, end: T) -> std::ops::Range<usize> {
+        let lo = self
+            .ranges
+            .partition_point(|r| r.end().succ().is_some_and(|after| after < start));
+        let hi = self
+            .ranges
+            .partition_point(|r| end.succ().is_none_or(|after| *r.start() <= after));
+        lo..hi
+    }
+
+    /// Add every value in `range`, merging it with any ranges it overlaps or
+    /// is adjacent to.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        let touching = self.touching(start, end);
+        if !touching.is_empty() {
+            start = start.min(*self.ranges[touching.start].start());
+            end = end.max(*self.ranges[touching.end - 1].end());
+        }
+
+        self.ranges.splice(touching, [start..=end]);
+    }
+
+    /// Take every value in `range` out of the set, splitting any range it
+    /// falls in the middle of.
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        let overlapping = self.overlapping(start, end);
+        if overlapping.is_empty() {
+            return;
+        }
+
+        let first = self.ranges[overlapping.start].clone();
+        let last = self.ranges[overlapping.end - 1].clone();
+
+        let mut remnants = Vec::with_capacity(2);
+        if *first.start() < start
+            && let Some(before) = start.pred()
+        {
+            remnants.push(*first.start()..=before);
+        }
+        if end < *last.end()
+            && let Some(after) = end.succ()
+        {
+            remnants.push(after..=*last.end());
+        }
+
+        self.ranges.splice(overlapping, remnants);
+    }
+
+    /// Every value in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut out = self.clone();
+        out.extend(other.iter().cloned());
+        out
+    }
+
+    /// Every value in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::default();
+        let mut left = self.ranges.iter().peekable();
+        let mut right = other.ranges.iter().peekable();
+
+        while let (Some(&a), Some(&b)) = (left.peek(), right.peek()) {
+            let start = *a.start().max(b.start());
+            let end = *a.end().min(b.end());
+            if start <= end {
+                ranges.push(start..=end);
+            }
+
+            // whichever ends first can't overlap anything else
+            if a.end() < b.end() {
+                left.next();
+            } else {
+                right.next();
+            }
+        }
+
+        Self { ranges }
+    }
+
+    /// Every value in this set that isn't in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut out = self.clone();
+        for range in other.iter() {
+            out.remove(range.clone());
+        }
+        out
+    }
+}
+
+impl<T: Bound> Extend<RangeInclusive<T>> for RangeSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: Bound> FromIterator<RangeInclusive<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = Self::default();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RangeSet<T> {
+    type Item = &'a RangeInclusive<T>;
+    type IntoIter = std::slice::Iter<'a, RangeInclusive<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    const LIMIT: u16 = 300;
+
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        /// Mostly short ranges, and the occasional empty one.
+        fn range(&mut self) -> RangeInclusive<u16> {
+            let start = (self.next() % LIMIT as u64) as u16;
+            let len = (self.next() % 40) as u16;
+            start..=(start + len).saturating_sub(1)
+        }
+    }
+
+    fn naive(set: &RangeSet<u16>) -> BTreeSet<u16> {
+        set.iter().flat_map(|r| r.clone()).collect()
+    }
+
+    fn check(set: &RangeSet<u16>, expected: &BTreeSet<u16>) {
+        assert_eq!(&naive(set), expected);
+        assert_eq!(set.len(), Some(expected.len() as u128));
+        assert_eq!(set.is_empty(), expected.is_empty());
+        for v in 0..LIMIT + 40 {
+            assert_eq!(set.contains(v), expected.contains(&v), "{}", v);
+        }
+
+        // sorted, non-empty, and never overlapping or adjacent
+        assert!(set.iter().all(|r| r.start() <= r.end()));
+        assert!(
+            set.iter()
+                .zip(set.iter().skip(1))
+                .all(|(a, b)| a.end().succ().is_some_and(|after| after < *b.start()))
+        );
+
+        // and so the same as inserting the values one at a time
+        let canonical: RangeSet<u16> = expected.iter().map(|v| *v..=*v).collect();
+        assert_eq!(set, &canonical);
+    }
+
+    #[test]
+    fn matches_naive_set() {
+        let mut rng = Rng(0x0123_4567_89ab_cdef);
+        for _ in 0..200 {
+            let mut set = RangeSet::new();
+            let mut expected = BTreeSet::default();
+            for _ in 0..30 {
+                let range = rng.range();
+                if rng.next().is_multiple_of(3) {
+                    set.remove(range.clone());
+                    for v in range {
+                        expected.remove(&v);
+                    }
+                } else {
+                    set.insert(range.clone());
+                    expected.extend(range);
+                }
+                check(&set, &expected);
+            }
+        }
+    }
+
+    #[test]
+    fn set_operations() {
+        let mut rng = Rng(0xfeed_face_dead_beef);
+        for _ in 0..500 {
+            let a: RangeSet<u16> = (0..rng.next() % 10).map(|_| rng.range()).collect();
+            let b: RangeSet<u16> = (0..rng.next() % 10).map(|_| rng.range()).collect();
+            let (na, nb) = (naive(&a), naive(&b));
+
+            check(&a.union(&b), &na.union(&nb).copied().collect());
+            check(
+                &a.intersection(&b),
+                &na.intersection(&nb).copied().collect(),
+            );
+            check(&a.difference(&b), &na.difference(&nb).copied().collect());
+        }
+    }
+
+    #[test]
+    fn adjacency() {
+        let mut set: RangeSet<u8> = [3..=5, 6..=8].into_iter().collect();
+        assert_eq!(set.as_slice(), [3..=8]);
+
+        // filling a gap of one joins both sides
+        set.insert(10..=12);
+        assert_eq!(set.range_count(), 2);
+        set.insert(9..=9);
+        assert_eq!(set.as_slice(), [3..=12]);
+
+        // and removing a single value splits them again
+        set.remove(9..=9);
+        assert_eq!(set.as_slice(), [3..=8, 10..=12]);
+
+        // the ends of the type can't be adjacent to anything past them
+        let set: RangeSet<u8> = [250..=255, 0..=5, 6..=249].into_iter().collect();
+        assert_eq!(set.as_slice(), [0..=255]);
+        assert_eq!(set.len(), Some(256));
+
+        let set: RangeSet<i8> = [-128..=-1, 0..=127].into_iter().collect();
+        assert_eq!(set.as_slice(), [-128..=127]);
+        assert_eq!(set.len(), Some(256));
+    }
+
+    #[test]
+    fn lengths() {
+        let set: RangeSet<u64> = [0..=u64::MAX].into_iter().collect();
+        assert_eq!(set.len(), Some(1 << 64));
+
+        let set: RangeSet<i64> = [i64::MIN..=i64::MAX].into_iter().collect();
+        assert_eq!(set.len(), Some(1 << 64));
+
+        let set: RangeSet<u128> = [0..=u128::MAX - 1].into_iter().collect();
+        assert_eq!(set.len(), Some(u128::MAX));
+
+        let set: RangeSet<u128> = [0..=u128::MAX].into_iter().collect();
+        assert_eq!(set.len(), None);
+
+        let set: RangeSet<u128> = [0..=u128::MAX / 2, u128::MAX / 2 + 2..=u128::MAX]
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), Some(u128::MAX));
+    }
+}
@@ -9,13 +9,57 @@ pub enum Op {
     Mul,
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+impl Op {
+    /// Combine two values, or `None` if the result overflows.
+    pub fn apply(self, a: u128, b: u128) -> Option<u128> {
+        match self {
+            Op::Add => a.checked_add(b),
+            Op::Mul => a.checked_mul(b),
+        }
+    }
+}
+
+/// A single problem, read both by rows (part one) and by columns (part two).
+///
+/// Problems can be any number of characters wide, and everything is computed
+/// in `u128`, with an error rather than wrapping if that overflows.
+///
+/// ```
+/// use trash_compactor::{Op, Value};
+///
+/// // the first problem from the puzzle's example
+/// let mut val = Value::new(0, Op::Mul);
+/// for row in ["123", " 45", "  6"] {
+///     for (idx, b) in row.bytes().enumerate().filter(|(_, b)| b.is_ascii_digit()) {
+///         val.insert_digit(idx, (b - b'0') as u128).unwrap();
+///     }
+///     val.append_normal(row.trim().parse().unwrap()).unwrap();
+/// }
+/// assert_eq!(val.normal, 123 * 45 * 6);
+/// // read down the columns, that's 1, 24 and 356
+/// assert_eq!(val.column_val().unwrap(), 8544);
+///
+/// // products past u64 are fine, but not past u128
+/// let mut val = Value::new(0, Op::Mul);
+/// val.append_normal(u64::MAX as u128).unwrap();
+/// val.append_normal(u64::MAX as u128).unwrap();
+/// assert!(val.append_normal(2).is_err());
+///
+/// // three columns of twenty 9s multiply to more than a u128 holds
+/// let mut val = Value::new(0, Op::Mul);
+/// for _ in 0..20 {
+///     for idx in 0..3 {
+///         val.insert_digit(idx, 9).unwrap();
+///     }
+/// }
+/// assert!(val.column_val().is_err());
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
 pub struct Value {
     pub col_idx: usize,
     pub last_idx: usize,
-    pub normal: u64,
-    column: [u64; 5],
-    max_col: usize,
+    pub normal: u128,
+    column: Vec<u128>,
     op: Op,
 }
 
@@ -24,42 +68,44 @@ impl Value {
         Self {
             col_idx,
             last_idx: usize::MAX,
-            column: [0; 5],
-            max_col: 0,
-            normal: op as u64,
+            column: Vec::default(),
+            normal: op as u128,
             op,
         }
     }
 
-    pub fn append_normal(&mut self, val: u64) {
-        match self.op {
-            Op::Add => self.normal += val,
-            Op::Mul => self.normal *= val,
-        }
+    pub fn append_normal(&mut self, val: u128) -> anyhow::Result<()> {
+        self.normal = self
+            .op
+            .apply(self.normal, val)
+            .ok_or_else(|| anyhow!("problem at column {} overflows", self.col_idx))?;
+        Ok(())
     }
 
-    pub fn insert_digit(&mut self, idx: usize, val: u64) {
-        self.column[idx] = 10 * self.column[idx] + val;
-        self.max_col = self.max_col.max(idx);
+    pub fn insert_digit(&mut self, idx: usize, val: u128) -> anyhow::Result<()> {
+        if idx >= self.column.len() {
+            self.column.resize(idx + 1, 0);
+        }
+
+        self.column[idx] = self.column[idx]
+            .checked_mul(10)
+            .and_then(|v| v.checked_add(val))
+            .ok_or_else(|| anyhow!("number in column {} overflows", self.col_idx + idx))?;
+        Ok(())
     }
 
-    pub fn column_val(&self) -> u64 {
-        self.column
-            .iter()
-            .take(self.max_col + 1)
-            .copied()
-            .reduce(|acc, e| match self.op {
-                Op::Add => acc + e,
-                Op::Mul => acc * e,
-            })
-            .unwrap_or_default()
+    pub fn column_val(&self) -> anyhow::Result<u128> {
+        let mut iter = self.column.iter().copied();
+        let first = iter.next().unwrap_or_default();
+        iter.try_fold(first, |acc, e| self.op.apply(acc, e))
+            .ok_or_else(|| anyhow!("problem at column {} overflows", self.col_idx))
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct TrashCompactor {
-    p1: u64,
-    p2: u64,
+    p1: u128,
+    p2: u128,
 }
 
 impl FromStr for TrashCompactor {
@@ -91,7 +137,7 @@ impl FromStr for TrashCompactor {
         for line in iter.rev() {
             let bytes = line.as_bytes();
             for val in vals.iter_mut() {
-                let mut cur = 0;
+                let mut cur: u128 = 0;
                 let stop = val.last_idx.min(line.len());
 
                 #[allow(clippy::needless_range_loop)]
@@ -99,20 +145,43 @@ impl FromStr for TrashCompactor {
                     let b = bytes[cur_idx];
                     if b.is_ascii_digit() {
                         let rel_idx = cur_idx - val.col_idx;
-                        let digit = (b - b'0') as u64;
-                        cur = cur * 10 + digit;
-                        val.insert_digit(rel_idx, digit);
+                        let digit = (b - b'0') as u128;
+                        cur = cur
+                            .checked_mul(10)
+                            .and_then(|c| c.checked_add(digit))
+                            .ok_or_else(|| anyhow!("number at column {} overflows", val.col_idx))?;
+                        val.insert_digit(rel_idx, digit)?;
                     }
                 }
 
                 if cur > 0 {
-                    val.append_normal(cur);
+                    val.append_normal(cur)?;
                 }
             }
         }
 
-        let p1 = vals.iter().map(|o| o.normal).sum();
-        let p2 = vals.iter().map(|o| o.column_val()).sum();
+        let mut p1: u128 = 0;
+        let mut p2: u128 = 0;
+        for val in vals.iter() {
+            let columns = val.column_val()?;
+            aoc_plumbing::trace!(
+                "problem",
+                col = val.col_idx,
+                op = match val.op {
+                    Op::Add => "+",
+                    Op::Mul => "*",
+                },
+                rows = val.normal,
+                columns = columns,
+            );
+
+            p1 = p1
+                .checked_add(val.normal)
+                .ok_or_else(|| anyhow!("grand total overflows"))?;
+            p2 = p2
+                .checked_add(columns)
+                .ok_or_else(|| anyhow!("grand total overflows"))?;
+        }
 
         Ok(Self { p1, p2 })
     }
@@ -124,8 +193,8 @@ impl Problem for TrashCompactor {
     const README: &'static str = include_str!("../README.md");
 
     type ProblemError = anyhow::Error;
-    type P1 = u64;
-    type P2 = u64;
+    type P1 = u128;
+    type P2 = u128;
 
     fn part_one(&mut self) -> Result<Self::P1, Self::ProblemError> {
         Ok(self.p1)
@@ -159,4 +228,40 @@ mod tests {
         let solution = TrashCompactor::solve(input).unwrap();
         assert_eq!(solution, Solution::new(4277556, 3263827));
     }
+
+    #[test]
+    fn wide_problems() {
+        // wider than the five columns problems used to be limited to
+        let input = "1234567 12
+     89 3
++       *  ";
+        let solution = TrashCompactor::solve(input).unwrap();
+        assert_eq!(
+            solution,
+            Solution::new(1234567 + 89 + 12 * 3, 1 + 2 + 3 + 4 + 5 + 68 + 79 + 13 * 2)
+        );
+
+        // products past u64 still fit in a u128
+        let input = format!("{}\n{}\n*", u64::MAX, u64::MAX);
+        let mut compactor: TrashCompactor = input.parse().unwrap();
+        let expected = u64::MAX as u128 * u64::MAX as u128;
+        assert_eq!(compactor.part_one().unwrap(), expected);
+    }
+
+    #[test]
+    fn overflow() {
+        let big = "9".repeat(20);
+        let input = format!("{}\n{}\n{}\n*", big, big, big);
+        assert!(TrashCompactor::solve(&input).is_err());
+
+        // a single number can be too long, too
+        let input = format!("{}\n+", "9".repeat(40));
+        assert!(TrashCompactor::solve(&input).is_err());
+
+        // and so can the totals
+        let line = format!("{} {}", u128::MAX, u128::MAX);
+        let pad = " ".repeat(u128::MAX.to_string().len());
+        let input = format!("{}\n+{}+", line, pad);
+        assert!(TrashCompactor::solve(&input).is_err());
+    }
 }
@@ -1,7 +1,10 @@
 use std::str::FromStr;
 
 use anyhow::anyhow;
-use aoc_plumbing::Problem;
+use aoc_plumbing::{
+    Problem,
+    viz::{Paint, Scene, Visualize},
+};
 
 #[derive(Debug, Clone)]
 pub struct Laboratories {
@@ -43,6 +46,7 @@ impl FromStr for Laboratories {
 
                     timelines[idx - 1] += prev;
                     timelines[idx + 1] += prev;
+                    aoc_plumbing::trace!("split", col = idx, timelines = prev, splits = p1);
 
                     offset = offset.min(idx - 1);
                 }
@@ -75,9 +79,52 @@ impl Problem for Laboratories {
     }
 }
 
+impl Visualize for Laboratories {
+    /// The manifold, with the path of every beam and which splitters they hit.
+    fn visualize(raw_input: &str) -> Result<Scene, Self::ProblemError> {
+        let grid: Vec<&[u8]> = raw_input.trim().lines().map(|l| l.as_bytes()).collect();
+        let width = grid.first().map(|r| r.len()).unwrap_or_default();
+        let mut scene = Scene::new(width as f64, grid.len() as f64);
+
+        let mut beams = vec![false; width];
+        for (row, line) in grid.iter().enumerate() {
+            let mut next = beams.clone();
+            for (col, ch) in line.iter().enumerate() {
+                match ch {
+                    b'S' => {
+                        scene.cell(col, row, Paint::Alert);
+                        next[col] = true;
+                    }
+                    b'^' if beams[col] => {
+                        scene.cell(col, row, Paint::Highlight);
+                        next[col] = false;
+                        if col > 0 {
+                            next[col - 1] = true;
+                        }
+                        if col + 1 < width {
+                            next[col + 1] = true;
+                        }
+                    }
+                    b'^' => scene.cell(col, row, Paint::Muted),
+                    _ if beams[col] => scene.cell(col, row, Paint::Primary),
+                    _ => {}
+                }
+            }
+            beams = next;
+        }
+
+        scene.legend(Paint::Alert, "start");
+        scene.legend(Paint::Primary, "beam");
+        scene.legend(Paint::Highlight, "splitter hit (part 1)");
+        scene.legend(Paint::Muted, "splitter missed");
+
+        Ok(scene)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use aoc_plumbing::Solution;
+    use aoc_plumbing::{Solution, viz::Shape};
 
     use super::*;
 
@@ -109,5 +156,21 @@ mod tests {
 ...............";
         let solution = Laboratories::solve(input).unwrap();
         assert_eq!(solution, Solution::new(21, 40));
+
+        let scene = Laboratories::visualize(input).unwrap();
+        let hit = scene
+            .shapes
+            .iter()
+            .filter(|s| {
+                matches!(
+                    s,
+                    Shape::Cell {
+                        paint: Paint::Highlight,
+                        ..
+                    }
+                )
+            })
+            .count();
+        assert_eq!(hit, 21);
     }
 }
@@ -1,7 +1,10 @@
 use std::{ops::Index, str::FromStr, usize};
 
 use anyhow::bail;
-use aoc_plumbing::Problem;
+use aoc_plumbing::{
+    Problem,
+    viz::{Paint, Scene, Visualize},
+};
 use aoc_std::{
     collections::BitSet,
     geometry::{AocBound, AocPoint, Bound3D, Point3D},
@@ -155,6 +158,7 @@ impl<const N: usize, const M: usize> FromStr for PlaygroundGen<N, M> {
                 if a != b {
                     groups -= 1;
                     disjoint_set.union(a, b);
+                    aoc_plumbing::trace!("connect", left = left, right = right, circuits = groups);
                 }
 
                 count += 1;
@@ -171,6 +175,7 @@ impl<const N: usize, const M: usize> FromStr for PlaygroundGen<N, M> {
                     }
 
                     sizes.sort_unstable();
+                    aoc_plumbing::trace!("circuits", connections = count, sizes = sizes);
 
                     p1 = sizes.iter().rev().take(3).product();
 
@@ -184,6 +189,12 @@ impl<const N: usize, const M: usize> FromStr for PlaygroundGen<N, M> {
                         if a != b {
                             groups -= 1;
                             disjoint_set.union(a, b);
+                            aoc_plumbing::trace!(
+                                "connect",
+                                left = left,
+                                right = right,
+                                circuits = groups
+                            );
 
                             if groups < 2 {
                                 let p2 = points[left].x * points[right].x;
@@ -208,6 +219,7 @@ impl<const N: usize, const M: usize> FromStr for PlaygroundGen<N, M> {
                 if a != b {
                     groups -= 1;
                     disjoint_set.union(a, b);
+                    aoc_plumbing::trace!("connect", left = left, right = right, circuits = groups);
 
                     if groups < 2 {
                         let p2 = points[left].x * points[right].x;
@@ -250,9 +262,107 @@ impl<const N: usize, const M: usize> Problem for PlaygroundGen<N, M> {
     }
 }
 
+impl<const N: usize, const M: usize> Visualize for PlaygroundGen<N, M> {
+    /// A top-down view of the junction boxes and the connections made between
+    /// them, with the three largest circuits after `M` connections
+    /// highlighted.
+    fn visualize(raw_input: &str) -> Result<Scene, Self::ProblemError> {
+        let mut points = Vec::with_capacity(N);
+        for line in raw_input.trim().lines() {
+            let (_, coord) = parse_coord(line).map_err(|e| e.to_owned())?;
+            points.push(coord);
+        }
+
+        let min_x = points.iter().map(|p| p.x).min().unwrap_or_default();
+        let max_x = points.iter().map(|p| p.x).max().unwrap_or_default();
+        let min_y = points.iter().map(|p| p.y).min().unwrap_or_default();
+        let max_y = points.iter().map(|p| p.y).max().unwrap_or_default();
+        let at = |idx: usize| {
+            (
+                (points[idx].x - min_x) as f64,
+                (points[idx].y - min_y) as f64,
+            )
+        };
+
+        let mut pairs = Vec::with_capacity(points.len() * points.len() / 2);
+        for left in 0..points.len() {
+            for right in (left + 1)..points.len() {
+                pairs.push((points[left].euclidean_dist_sq(&points[right]), left, right));
+            }
+        }
+        pairs.sort_unstable();
+
+        let mut scene = Scene::new((max_x - min_x) as f64, (max_y - min_y) as f64);
+        let mut disjoint_set = DisjointSet::with_capacity(points.len());
+        for i in 0..points.len() {
+            disjoint_set.insert(i);
+        }
+
+        let mut in_largest = vec![false; points.len()];
+        let mut groups = points.len();
+        for (count, (_, left, right)) in pairs.into_iter().enumerate() {
+            if count == M {
+                let roots: Vec<_> = (0..points.len()).map(|i| disjoint_set.find(i)).collect();
+                let mut circuits = roots.clone();
+                circuits.sort_unstable();
+                circuits.dedup();
+                circuits.sort_unstable_by_key(|&r| std::cmp::Reverse(disjoint_set[r].size));
+                circuits.truncate(3);
+
+                for (flag, root) in in_largest.iter_mut().zip(roots) {
+                    *flag = circuits.contains(&root);
+                }
+            }
+
+            let paint = if count < M {
+                Paint::Secondary
+            } else {
+                Paint::Muted
+            };
+
+            let a = disjoint_set.find(left);
+            let b = disjoint_set.find(right);
+            if a == b {
+                // only the first connections are drawn even if they're
+                // redundant, as they still count towards part 1
+                if count < M {
+                    scene.line(at(left), at(right), paint);
+                }
+                continue;
+            }
+
+            disjoint_set.union(a, b);
+            groups -= 1;
+
+            if groups < 2 {
+                scene.line(at(left), at(right), Paint::Highlight);
+                break;
+            }
+
+            scene.line(at(left), at(right), paint);
+        }
+
+        for (idx, in_largest) in in_largest.into_iter().enumerate() {
+            let paint = if in_largest {
+                Paint::Primary
+            } else {
+                Paint::Muted
+            };
+            scene.dot(at(idx), paint);
+        }
+
+        scene.legend(Paint::Primary, "three largest circuits (part 1)");
+        scene.legend(Paint::Secondary, "first connections (part 1)");
+        scene.legend(Paint::Muted, "later connections");
+        scene.legend(Paint::Highlight, "final connection (part 2)");
+
+        Ok(scene)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use aoc_plumbing::Solution;
+    use aoc_plumbing::{Solution, viz::Shape};
 
     use super::*;
 
@@ -288,5 +398,32 @@ mod tests {
 425,690,689";
         let solution = PlaygroundGen::<20, 10>::solve(input).unwrap();
         assert_eq!(solution, Solution::new(40, 25272));
+
+        let scene = PlaygroundGen::<20, 10>::visualize(input).unwrap();
+        let lines = |paint| {
+            scene
+                .shapes
+                .iter()
+                .filter(|s| matches!(s, Shape::Line { paint: p, .. } if *p == paint))
+                .count()
+        };
+        assert_eq!(lines(Paint::Secondary), 10);
+        assert_eq!(lines(Paint::Highlight), 1);
+
+        // circuits of 5, 4 and 2
+        let in_largest = scene
+            .shapes
+            .iter()
+            .filter(|s| {
+                matches!(
+                    s,
+                    Shape::Dot {
+                        paint: Paint::Primary,
+                        ..
+                    }
+                )
+            })
+            .count();
+        assert_eq!(in_largest, 11);
     }
 }
@@ -1,7 +1,10 @@
 use std::str::FromStr;
 
 use anyhow::anyhow;
-use aoc_plumbing::Problem;
+use aoc_plumbing::{
+    Problem,
+    viz::{Paint, Scene, Visualize},
+};
 use aoc_std::geometry::{Point2D, Rectangle};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -131,6 +134,13 @@ impl FromStr for MovieTheater {
                 let area = rect.area();
                 if area > p1 {
                     p1 = area;
+                    aoc_plumbing::trace!(
+                        "largest",
+                        part = 1,
+                        a = (a.x, a.y),
+                        b = (b.x, b.y),
+                        area = area
+                    );
                 }
             }
         }
@@ -208,6 +218,13 @@ fn find_largest(
             }
 
             max = area;
+            aoc_plumbing::trace!(
+                "largest",
+                part = 2,
+                a = (cur.x, cur.y),
+                b = (fixed.x, fixed.y),
+                area = area
+            );
         }
     } else {
         'outer: loop {
@@ -237,6 +254,13 @@ fn find_largest(
             }
 
             max = area;
+            aoc_plumbing::trace!(
+                "largest",
+                part = 2,
+                a = (cur.x, cur.y),
+                b = (fixed.x, fixed.y),
+                area = area
+            );
         }
     }
 
@@ -261,9 +285,117 @@ impl Problem for MovieTheater {
     }
 }
 
+/// Whether `rect` lies entirely within the loop.
+///
+/// As every edge is axis aligned, it's enough that no edge passes through the
+/// interior of the rectangle and that its center is enclosed.
+fn enclosed(points: &[Point2D<usize>], rect: &Rectangle<usize>) -> bool {
+    let (lo, hi) = (rect.p1(), rect.p2());
+    let mut prev = points[points.len() - 1];
+
+    for point in points {
+        let (a, b) = (prev, *point);
+        prev = *point;
+
+        let cuts = if a.y == b.y {
+            lo.y < a.y && a.y < hi.y && a.x.min(b.x).max(lo.x) < a.x.max(b.x).min(hi.x)
+        } else {
+            lo.x < a.x && a.x < hi.x && a.y.min(b.y).max(lo.y) < a.y.max(b.y).min(hi.y)
+        };
+
+        if cuts {
+            return false;
+        }
+    }
+
+    encloses(points, lo.x + hi.x, lo.y + hi.y)
+}
+
+/// Whether the point at (`x2` / 2, `y2` / 2) is inside or on the loop, taking
+/// doubled coordinates so the centers of rectangles stay integral.
+fn encloses(points: &[Point2D<usize>], x2: usize, y2: usize) -> bool {
+    let mut inside = false;
+    let mut prev = points[points.len() - 1];
+
+    for point in points {
+        let (ax, ay, bx, by) = (prev.x * 2, prev.y * 2, point.x * 2, point.y * 2);
+        prev = *point;
+
+        let on_x = ax.min(bx) <= x2 && x2 <= ax.max(bx);
+        let on_y = ay.min(by) <= y2 && y2 <= ay.max(by);
+        if on_x && on_y {
+            // every edge is axis aligned, so this is on the edge
+            return true;
+        }
+
+        // cast a ray towards +x, counting the vertical edges it crosses
+        if ax == bx && ax > x2 && ay.min(by) <= y2 && y2 < ay.max(by) {
+            inside = !inside;
+        }
+    }
+
+    inside
+}
+
+impl Visualize for MovieTheater {
+    /// The loop of red tiles, with the largest rectangle overall and the
+    /// largest one that stays inside the loop.
+    ///
+    /// This finds the part 2 rectangle by brute force rather than relying on
+    /// the shape of the input like the solution does.
+    fn visualize(raw_input: &str) -> Result<Scene, Self::ProblemError> {
+        let mut points = Vec::default();
+        for line in raw_input.trim().lines() {
+            let (rx, ry) = line
+                .split_once(',')
+                .ok_or_else(|| anyhow!("invalid input"))?;
+            points.push(Point2D::<usize>::new(rx.parse()?, ry.parse()?));
+        }
+
+        let min_x = points.iter().map(|p| p.x).min().unwrap_or_default();
+        let max_x = points.iter().map(|p| p.x).max().unwrap_or_default();
+        let min_y = points.iter().map(|p| p.y).min().unwrap_or_default();
+        let max_y = points.iter().map(|p| p.y).max().unwrap_or_default();
+        let at = |p: &Point2D<usize>| ((p.x - min_x) as f64, (p.y - min_y) as f64);
+
+        let mut rects = Vec::default();
+        for (i, a) in points.iter().enumerate() {
+            for b in points.iter().skip(i + 1) {
+                let rect = Rectangle::new(*a, *b);
+                rects.push((rect.area(), *a, *b, rect));
+            }
+        }
+        rects.sort_unstable_by(|l, r| r.0.cmp(&l.0));
+
+        let mut scene = Scene::new((max_x - min_x) as f64, (max_y - min_y) as f64);
+        scene.polygon(points.iter().map(at).collect(), Paint::Primary);
+
+        if let Some((_, a, b, _)) = rects.first() {
+            scene.rect(at(a), at(b), Paint::Secondary);
+        }
+
+        if let Some((_, a, b, _)) = rects.iter().find(|(_, _, _, rect)| enclosed(&points, rect)) {
+            scene.rect(at(a), at(b), Paint::Highlight);
+        }
+
+        for point in &points {
+            scene.dot(at(point), Paint::Alert);
+        }
+
+        scene.legend(Paint::Alert, "red tiles");
+        scene.legend(Paint::Secondary, "largest rectangle (part 1)");
+        scene.legend(Paint::Highlight, "largest enclosed rectangle (part 2)");
+
+        Ok(scene)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use aoc_plumbing::Solution;
+    use aoc_plumbing::{
+        Solution,
+        viz::{Coord, Shape},
+    };
 
     use super::*;
 
@@ -287,5 +419,18 @@ mod tests {
 7,3";
         let solution = MovieTheater::solve(input).unwrap();
         assert_eq!(solution, Solution::new(50, 24));
+
+        let scene = MovieTheater::visualize(input).unwrap();
+        let area = |paint| {
+            scene.shapes.iter().find_map(|s| match s {
+                Shape::Polygon { points, paint: p } if *p == paint => {
+                    let (a, b): (Coord, Coord) = (points[0], points[2]);
+                    Some(((a.0 - b.0).abs() + 1.0) * ((a.1 - b.1).abs() + 1.0))
+                }
+                _ => None,
+            })
+        };
+        assert_eq!(area(Paint::Secondary), Some(50.0));
+        assert_eq!(area(Paint::Highlight), Some(24.0));
     }
 }
@@ -388,10 +388,20 @@ impl FromStr for Factory {
         let mut indicators_next = Vec::with_capacity(1024);
         let mut p1 = 0;
         let mut p2 = 0;
-        for line in s.trim().lines() {
+        for (idx, line) in s.trim().lines().enumerate() {
             let (_, machine) = parse_machine(line).map_err(|e| e.to_owned())?;
-            p1 += machine.fewest_indicator_presses(&mut indicators_front, &mut indicators_next);
-            p2 += machine.fewest_joltage_presses();
+            let indicator =
+                machine.fewest_indicator_presses(&mut indicators_front, &mut indicators_next);
+            let joltage = machine.fewest_joltage_presses();
+            aoc_plumbing::trace!(
+                "machine",
+                machine = idx,
+                indicator = indicator,
+                joltage = joltage
+            );
+
+            p1 += indicator;
+            p2 += joltage;
         }
 
         Ok(Self { p1, p2 })
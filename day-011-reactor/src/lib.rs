@@ -74,6 +74,12 @@ impl FromStr for Reactor {
         let mut cache = vec![None; nodes.len()];
         let State { both: p2, .. } = explore(svr, out, &nodes, &mut cache);
         let p1 = cache[you].take().unwrap_or_default().none;
+        aoc_plumbing::trace!(
+            "paths",
+            nodes = nodes.len(),
+            you_to_out = p1,
+            svr_to_out_via_both = p2,
+        );
 
         Ok(Self { p1, p2 })
     }
@@ -27,6 +27,7 @@ impl FromStr for ChristmasTreeFarm {
                 .split_once('x')
                 .ok_or_else(|| anyhow!("invalid input"))?;
             let area = (w.parse::<u64>()? * h.parse::<u64>()?) / 9;
+            aoc_plumbing::trace!("region", region = left, required = required, blocks = area);
 
             if area >= required {
                 p1 += 1;
@@ -3,9 +3,9 @@ use std::str::FromStr;
 use aoc_plumbing::Problem;
 
 #[derive(Debug, Clone)]
-pub struct {{project-name|upper_camel_case}};
+pub struct {{struct_name}};
 
-impl FromStr for {{project-name|upper_camel_case}} {
+impl FromStr for {{struct_name}} {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -13,9 +13,10 @@ impl FromStr for {{project-name|upper_camel_case}} {
     }
 }
 
-impl Problem for {{project-name|upper_camel_case}} {
+impl Problem for {{struct_name}} {
+    const YEAR: usize = {{year}};
     const DAY: usize = {{day}};
-    const TITLE: &'static str = "{{project-name|title_case|downcase}}";
+    const TITLE: &'static str = "{{title}}";
     const README: &'static str = include_str!("../README.md");
 
     type ProblemError = anyhow::Error;
@@ -41,14 +42,14 @@ mod tests {
     #[ignore]
     fn full_dataset() {
         let input = std::fs::read_to_string("input.txt").expect("Unable to load input");
-        let solution = {{project-name|upper_camel_case}}::solve(&input).unwrap();
+        let solution = {{struct_name}}::solve(&input).unwrap();
         assert_eq!(solution, Solution::new(0, 0));
     }
 
     #[test]
     fn example() {
         let input = "";
-        let solution = {{project-name|upper_camel_case}}::solve(input).unwrap();
+        let solution = {{struct_name}}::solve(input).unwrap();
         assert_eq!(solution, Solution::new(0, 0));
     }
 }